@@ -4,4 +4,14 @@ use anchor_lang::prelude::*;
 pub enum GplCompressionError {
     #[msg("Invalid authority provided")]
     AssetIDNotFound,
+    #[msg("Invalid merkle tree account provided")]
+    InvalidMerkleTree,
+    #[msg("The post fields do not match the target post")]
+    InvalidTargetPost,
+    #[msg("The signer is not allowed to write to the tree")]
+    UnauthorizedTreeWriter,
+    #[msg("Too many delegates for the tree")]
+    TooManyDelegates,
+    #[msg("The tree config is not a legacy tree config")]
+    InvalidLegacyTreeConfig,
 }
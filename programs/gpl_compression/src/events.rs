@@ -6,6 +6,7 @@ pub struct CompressedPostNew {
     pub post_id: Pubkey,
     pub post_bump: u8,
     pub index: u32,
+    pub root: [u8; 32],
     pub profile: Pubkey,
     pub random_hash: [u8; 32],
     pub metadata_uri: String,
@@ -42,8 +43,10 @@ pub struct CompressedConnectionNew {
     pub connection_id: Pubkey,
     pub connection_bump: u8,
     pub index: u32,
+    pub root: [u8; 32],
     pub from_profile: Pubkey,
     pub to_profile: Pubkey,
+    pub kind: u8,
     pub timestamp: i64,
 }
 
@@ -55,6 +58,7 @@ pub struct CompressedConnectionDeleted {
     pub index: u32,
     pub from_profile: Pubkey,
     pub to_profile: Pubkey,
+    pub kind: u8,
     pub timestamp: i64,
 }
 
@@ -64,6 +68,7 @@ pub struct CompressedReactionNew {
     pub reaction_id: Pubkey,
     pub reaction_bump: u8,
     pub index: u32,
+    pub root: [u8; 32],
     pub from_profile: Pubkey,
     pub to_post: Pubkey,
     pub reaction_type: String,
@@ -88,9 +93,64 @@ pub struct CompressedCommentNew {
     pub post_id: Pubkey,
     pub post_bump: u8,
     pub index: u32,
+    pub root: [u8; 32],
     pub reply_to: Pubkey,
     pub profile: Pubkey,
     pub random_hash: [u8; 32],
     pub metadata_uri: String,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct CompressedBadgeNew {
+    pub asset_id: Pubkey,
+    pub badge_id: Pubkey,
+    pub badge_bump: u8,
+    pub index: u32,
+    pub root: [u8; 32],
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub schema: Pubkey,
+    pub metadata_uri: String,
+    pub expires_at: Option<i64>,
+    pub attributes: Vec<u8>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompressedBadgeUpdated {
+    pub asset_id: Pubkey,
+    pub badge_id: Pubkey,
+    pub badge_bump: u8,
+    pub index: u32,
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub schema: Pubkey,
+    pub metadata_uri: String,
+    pub attributes: Vec<u8>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompressedBadgeAccepted {
+    pub asset_id: Pubkey,
+    pub badge_id: Pubkey,
+    pub badge_bump: u8,
+    pub index: u32,
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub schema: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompressedBadgeBurned {
+    pub asset_id: Pubkey,
+    pub badge_id: Pubkey,
+    pub badge_bump: u8,
+    pub index: u32,
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub schema: Pubkey,
+    pub timestamp: i64,
+}
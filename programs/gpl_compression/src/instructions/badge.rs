@@ -0,0 +1,577 @@
+use crate::errors::GplCompressionError;
+use crate::events::{
+    CompressedBadgeAccepted, CompressedBadgeBurned, CompressedBadgeNew, CompressedBadgeUpdated,
+};
+use crate::state::{CompressedBadge, TreeConfig};
+use crate::utils::{
+    append_leaf, get_rightmost_index, get_root, replace_leaf, try_find_asset_id, LeafSchema,
+};
+
+use gpl_core::errors::GumError;
+use spl_account_compression::wrap_application_data_v1;
+use spl_account_compression::Node;
+
+use gpl_core::state::{Badge, Issuer, Profile, Schema, MAX_LEN_URI};
+
+use anchor_lang::prelude::*;
+use std::convert::AsRef;
+
+use gpl_core::constants::*;
+
+use anchor_lang::solana_program::keccak::hashv;
+use gpl_core::program::GplCore;
+use spl_account_compression::program::SplAccountCompression;
+use spl_account_compression::Noop;
+
+// Create Badge
+// Compressed badges can not be revoked, the issuer burns them instead
+#[derive(Accounts)]
+#[instruction(metadata_uri: String, expires_at: Option<i64>, attributes: Vec<u8>)]
+pub struct CreateCompressedBadge<'info> {
+    #[account(
+        seeds = [Issuer::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        seeds::program = gpl_core_program.key(),
+        bump,
+        has_one = authority,
+        constraint = issuer.can_issue() @ GumError::UnverifiedIssuer
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            holder.random_hash.as_ref(),
+        ],
+        seeds::program = gpl_core_program.key(),
+        bump,
+    )]
+    pub holder: Account<'info, Profile>,
+
+    #[account(
+        seeds = [Schema::SEED_PREFIX.as_bytes(), schema.random_hash.as_ref()],
+        seeds::program = gpl_core_program.key(),
+        bump,
+        constraint = schema.can_be_used_by(&issuer.key()) @ GumError::SchemaNotOwnedByIssuer
+    )]
+    pub schema: Account<'info, Schema>,
+
+    #[account(
+        seeds = [merkle_tree.key.as_ref()],
+        bump,
+        constraint = tree_config.can_write(&authority.key(), delegate.as_ref().map(|d| d.key())) @ GplCompressionError::UnauthorizedTreeWriter
+    )]
+    pub tree_config: Account<'info, TreeConfig>,
+
+    #[account(mut)]
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // Signs for a program writing to a restricted tree as one of its delegates
+    pub delegate: Option<Signer<'info>>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper_program: Program<'info, Noop>,
+    pub gpl_core_program: Program<'info, GplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to create a new Badge
+pub fn create_compressed_badge_handler(
+    ctx: Context<CreateCompressedBadge>,
+    metadata_uri: String,
+    expires_at: Option<i64>,
+    attributes: Vec<u8>,
+) -> Result<()> {
+    require!(metadata_uri.len() <= MAX_LEN_URI, GumError::URITooLong);
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            GumError::InvalidBadgeExpiry
+        );
+    }
+    ctx.accounts.schema.validate_attributes(&attributes)?;
+
+    let issuer = ctx.accounts.issuer.key();
+    let schema = ctx.accounts.schema.key();
+    let holder = ctx.accounts.holder.key();
+
+    let badge_seeds = [
+        Badge::SEED_PREFIX.as_bytes(),
+        issuer.as_ref(),
+        schema.as_ref(),
+        holder.as_ref(),
+    ];
+
+    let (badge_id, badge_bump) =
+        Pubkey::try_find_program_address(&badge_seeds, &GplCore::id()).unwrap();
+
+    let seed_hash = hashv(&badge_seeds).to_bytes();
+
+    let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
+
+    let badge = CompressedBadge {
+        issuer,
+        holder,
+        update_authority: ctx.accounts.authority.key(),
+        schema,
+        metadata_uri,
+        expires_at,
+        revoked: false,
+        attributes,
+        pending: ctx.accounts.schema.requires_acceptance,
+    };
+
+    let leaf = LeafSchema {
+        asset_id,
+        seed_hash,
+        data_hash: badge.data_hash()?,
+    };
+
+    let leaf_node = leaf.to_node()?;
+
+    wrap_application_data_v1(leaf_node.to_vec(), &ctx.accounts.log_wrapper_program)?;
+
+    let index = get_rightmost_index(&ctx.accounts.merkle_tree)?;
+
+    append_leaf(
+        ctx.accounts.merkle_tree.key,
+        ctx.bumps["tree_config"],
+        &ctx.accounts.tree_config.to_account_info(),
+        leaf_node,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.compression_program,
+        &ctx.accounts.log_wrapper_program,
+    )?;
+
+    let root = get_root(&ctx.accounts.merkle_tree)?;
+
+    emit!(CompressedBadgeNew {
+        asset_id,
+        badge_id,
+        badge_bump,
+        issuer,
+        holder,
+        schema,
+        metadata_uri: badge.metadata_uri.clone(),
+        expires_at,
+        attributes: badge.attributes.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+        index,
+        root,
+    });
+
+    Ok(())
+}
+
+// Update a badge
+#[derive(Accounts)]
+#[instruction(metadata_uri: String, new_metadata_uri: String, expires_at: Option<i64>, attributes: Vec<u8>, new_attributes: Vec<u8>, pending: bool, root: [u8;32], index: u32)]
+pub struct UpdateCompressedBadge<'info> {
+    #[account(
+        seeds = [Issuer::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        seeds::program = gpl_core_program.key(),
+        bump,
+        has_one = authority,
+        constraint = issuer.can_issue() @ GumError::UnverifiedIssuer
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            holder.random_hash.as_ref(),
+        ],
+        seeds::program = gpl_core_program.key(),
+        bump,
+    )]
+    pub holder: Account<'info, Profile>,
+
+    #[account(
+        seeds = [Schema::SEED_PREFIX.as_bytes(), schema.random_hash.as_ref()],
+        seeds::program = gpl_core_program.key(),
+        bump,
+    )]
+    pub schema: Account<'info, Schema>,
+
+    #[account(seeds = [merkle_tree.key.as_ref()], bump)]
+    pub tree_config: Account<'info, TreeConfig>,
+
+    #[account(mut)]
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper_program: Program<'info, Noop>,
+    pub gpl_core_program: Program<'info, GplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to update a Badge
+pub fn update_compressed_badge_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdateCompressedBadge<'info>>,
+    metadata_uri: String,
+    new_metadata_uri: String,
+    expires_at: Option<i64>,
+    attributes: Vec<u8>,
+    new_attributes: Vec<u8>,
+    pending: bool,
+    root: [u8; 32],
+    index: u32,
+) -> Result<()> {
+    require!(new_metadata_uri.len() <= MAX_LEN_URI, GumError::URITooLong);
+    ctx.accounts.schema.validate_attributes(&new_attributes)?;
+
+    let issuer = ctx.accounts.issuer.key();
+    let schema = ctx.accounts.schema.key();
+    let holder = ctx.accounts.holder.key();
+
+    let badge_seeds = [
+        Badge::SEED_PREFIX.as_bytes(),
+        issuer.as_ref(),
+        schema.as_ref(),
+        holder.as_ref(),
+    ];
+
+    let (badge_id, badge_bump) =
+        Pubkey::try_find_program_address(&badge_seeds, &GplCore::id()).unwrap();
+
+    let seed_hash = hashv(&badge_seeds).to_bytes();
+
+    let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
+
+    let old_badge = CompressedBadge {
+        issuer,
+        holder,
+        update_authority: ctx.accounts.authority.key(),
+        schema,
+        metadata_uri,
+        expires_at,
+        revoked: false,
+        attributes,
+        pending,
+    };
+
+    let old_leaf = LeafSchema {
+        asset_id,
+        seed_hash,
+        data_hash: old_badge.data_hash()?,
+    };
+
+    let old_leaf_node = old_leaf.to_node()?;
+
+    let new_badge = CompressedBadge {
+        metadata_uri: new_metadata_uri,
+        attributes: new_attributes,
+        ..old_badge
+    };
+
+    let new_leaf = LeafSchema {
+        asset_id,
+        seed_hash,
+        data_hash: new_badge.data_hash()?,
+    };
+
+    let new_leaf_node = new_leaf.to_node()?;
+
+    wrap_application_data_v1(new_leaf_node.to_vec(), &ctx.accounts.log_wrapper_program)?;
+
+    replace_leaf(
+        ctx.accounts.merkle_tree.key,
+        ctx.bumps["tree_config"],
+        &ctx.accounts.tree_config.to_account_info(),
+        &ctx.accounts.merkle_tree,
+        root,
+        old_leaf_node,
+        new_leaf_node,
+        index,
+        ctx.remaining_accounts,
+        &ctx.accounts.compression_program,
+        &ctx.accounts.log_wrapper_program,
+    )?;
+
+    emit!(CompressedBadgeUpdated {
+        asset_id,
+        badge_id,
+        badge_bump,
+        issuer,
+        holder,
+        schema,
+        metadata_uri: new_badge.metadata_uri.clone(),
+        attributes: new_badge.attributes.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+        index,
+    });
+
+    Ok(())
+}
+
+// Accept a pending badge
+// Badges of a schema requiring acceptance are created pending, the holder accepts them
+#[derive(Accounts)]
+#[instruction(metadata_uri: String, expires_at: Option<i64>, attributes: Vec<u8>, root: [u8;32], index: u32)]
+pub struct AcceptCompressedBadge<'info> {
+    #[account(
+        seeds = [Issuer::SEED_PREFIX.as_bytes(), issuer.authority.key().as_ref()],
+        seeds::program = gpl_core_program.key(),
+        bump,
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            holder.random_hash.as_ref(),
+        ],
+        seeds::program = gpl_core_program.key(),
+        bump,
+        has_one = authority,
+    )]
+    pub holder: Account<'info, Profile>,
+
+    #[account(
+        seeds = [Schema::SEED_PREFIX.as_bytes(), schema.random_hash.as_ref()],
+        seeds::program = gpl_core_program.key(),
+        bump,
+    )]
+    pub schema: Account<'info, Schema>,
+
+    #[account(seeds = [merkle_tree.key.as_ref()], bump)]
+    pub tree_config: Account<'info, TreeConfig>,
+
+    #[account(mut)]
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper_program: Program<'info, Noop>,
+    pub gpl_core_program: Program<'info, GplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to accept a compressed badge
+pub fn accept_compressed_badge_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AcceptCompressedBadge<'info>>,
+    metadata_uri: String,
+    expires_at: Option<i64>,
+    attributes: Vec<u8>,
+    root: [u8; 32],
+    index: u32,
+) -> Result<()> {
+    let issuer = ctx.accounts.issuer.key();
+    let schema = ctx.accounts.schema.key();
+    let holder = ctx.accounts.holder.key();
+
+    let badge_seeds = [
+        Badge::SEED_PREFIX.as_bytes(),
+        issuer.as_ref(),
+        schema.as_ref(),
+        holder.as_ref(),
+    ];
+
+    let (badge_id, badge_bump) =
+        Pubkey::try_find_program_address(&badge_seeds, &GplCore::id()).unwrap();
+
+    let seed_hash = hashv(&badge_seeds).to_bytes();
+
+    let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
+
+    // The old leaf only verifies if the badge is still pending
+    let old_badge = CompressedBadge {
+        issuer,
+        holder,
+        update_authority: ctx.accounts.issuer.authority,
+        schema,
+        metadata_uri,
+        expires_at,
+        revoked: false,
+        attributes,
+        pending: true,
+    };
+
+    let old_leaf = LeafSchema {
+        asset_id,
+        seed_hash,
+        data_hash: old_badge.data_hash()?,
+    };
+
+    let old_leaf_node = old_leaf.to_node()?;
+
+    let new_badge = CompressedBadge {
+        pending: false,
+        ..old_badge
+    };
+
+    let new_leaf = LeafSchema {
+        asset_id,
+        seed_hash,
+        data_hash: new_badge.data_hash()?,
+    };
+
+    let new_leaf_node = new_leaf.to_node()?;
+
+    wrap_application_data_v1(new_leaf_node.to_vec(), &ctx.accounts.log_wrapper_program)?;
+
+    replace_leaf(
+        ctx.accounts.merkle_tree.key,
+        ctx.bumps["tree_config"],
+        &ctx.accounts.tree_config.to_account_info(),
+        &ctx.accounts.merkle_tree,
+        root,
+        old_leaf_node,
+        new_leaf_node,
+        index,
+        ctx.remaining_accounts,
+        &ctx.accounts.compression_program,
+        &ctx.accounts.log_wrapper_program,
+    )?;
+
+    emit!(CompressedBadgeAccepted {
+        asset_id,
+        badge_id,
+        badge_bump,
+        issuer,
+        holder,
+        schema,
+        timestamp: Clock::get()?.unix_timestamp,
+        index
+    });
+
+    Ok(())
+}
+
+// Burn a badge
+// Either the holder or the issuer can burn a badge
+#[derive(Accounts)]
+#[instruction(metadata_uri: String, expires_at: Option<i64>, attributes: Vec<u8>, pending: bool, root: [u8;32], index: u32)]
+pub struct BurnCompressedBadge<'info> {
+    #[account(
+        seeds = [Issuer::SEED_PREFIX.as_bytes(), issuer.authority.key().as_ref()],
+        seeds::program = gpl_core_program.key(),
+        bump,
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            holder.random_hash.as_ref(),
+        ],
+        seeds::program = gpl_core_program.key(),
+        bump,
+    )]
+    pub holder: Account<'info, Profile>,
+
+    #[account(
+        seeds = [Schema::SEED_PREFIX.as_bytes(), schema.random_hash.as_ref()],
+        seeds::program = gpl_core_program.key(),
+        bump,
+    )]
+    pub schema: Account<'info, Schema>,
+
+    #[account(seeds = [merkle_tree.key.as_ref()], bump)]
+    pub tree_config: Account<'info, TreeConfig>,
+
+    #[account(mut)]
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == holder.authority || authority.key() == issuer.authority @ProgramError::MissingRequiredSignature
+    )]
+    pub authority: Signer<'info>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper_program: Program<'info, Noop>,
+    pub gpl_core_program: Program<'info, GplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to burn a compressed badge
+pub fn burn_compressed_badge_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, BurnCompressedBadge<'info>>,
+    metadata_uri: String,
+    expires_at: Option<i64>,
+    attributes: Vec<u8>,
+    pending: bool,
+    root: [u8; 32],
+    index: u32,
+) -> Result<()> {
+    let issuer = ctx.accounts.issuer.key();
+    let schema = ctx.accounts.schema.key();
+    let holder = ctx.accounts.holder.key();
+
+    let badge_seeds = [
+        Badge::SEED_PREFIX.as_bytes(),
+        issuer.as_ref(),
+        schema.as_ref(),
+        holder.as_ref(),
+    ];
+
+    let (badge_id, badge_bump) =
+        Pubkey::try_find_program_address(&badge_seeds, &GplCore::id()).unwrap();
+
+    let seed_hash = hashv(&badge_seeds).to_bytes();
+
+    let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
+
+    // Compressed badges are always created with the issuer authority as the update authority
+    let old_badge = CompressedBadge {
+        issuer,
+        holder,
+        update_authority: ctx.accounts.issuer.authority,
+        schema,
+        metadata_uri,
+        expires_at,
+        revoked: false,
+        attributes,
+        pending,
+    };
+
+    let old_leaf = LeafSchema {
+        asset_id,
+        seed_hash,
+        data_hash: old_badge.data_hash()?,
+    };
+
+    let old_leaf_node = old_leaf.to_node()?;
+
+    let new_leaf_node = Node::default();
+
+    wrap_application_data_v1(new_leaf_node.to_vec(), &ctx.accounts.log_wrapper_program)?;
+
+    replace_leaf(
+        ctx.accounts.merkle_tree.key,
+        ctx.bumps["tree_config"],
+        &ctx.accounts.tree_config.to_account_info(),
+        &ctx.accounts.merkle_tree,
+        root,
+        old_leaf_node,
+        new_leaf_node,
+        index,
+        ctx.remaining_accounts,
+        &ctx.accounts.compression_program,
+        &ctx.accounts.log_wrapper_program,
+    )?;
+
+    emit!(CompressedBadgeBurned {
+        asset_id,
+        badge_id,
+        badge_bump,
+        issuer,
+        holder,
+        schema,
+        timestamp: Clock::get()?.unix_timestamp,
+        index,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use std::convert::AsRef;
 
-use gpl_core::state::{Post, Profile};
+use gpl_core::state::Profile;
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::keccak::hashv;
@@ -15,14 +15,17 @@ use spl_account_compression::program::SplAccountCompression;
 use spl_account_compression::wrap_application_data_v1;
 use spl_account_compression::Noop;
 
+use crate::errors::GplCompressionError;
 use crate::events::CompressedCommentNew;
-use crate::state::TreeConfig;
+use crate::state::{CompressedPost, TreeConfig};
 use crate::utils::LeafSchema;
-use crate::utils::{append_leaf, try_find_asset_id, verify_leaf};
+use crate::utils::{
+    append_leaf, get_rightmost_index, get_root, post_leaf_node, try_find_asset_id, verify_leaf,
+};
 
 // Create Comment
 #[derive(Accounts)]
-#[instruction(reply_to: Pubkey, metadata_uri: String, random_hash: [u8; 32], post_root: [u8; 32], post_leaf: [u8; 32], post_index: u32)]
+#[instruction(reply_to: Pubkey, metadata_uri: String, random_hash: [u8; 32], post_profile: Pubkey, post_metadata_uri: String, post_random_hash: [u8; 32], post_reply_to: Option<Pubkey>, post_root: [u8; 32], post_index: u32)]
 pub struct CreateCompressedComment<'info> {
     #[account(
         seeds = [
@@ -35,23 +38,30 @@ pub struct CreateCompressedComment<'info> {
     )]
     pub from_profile: Account<'info, Profile>,
 
-    #[account(seeds = [merkle_tree.key.as_ref()], bump)]
+    #[account(
+        seeds = [merkle_tree.key.as_ref()],
+        bump,
+        constraint = tree_config.can_write(&authority.key(), delegate.as_ref().map(|d| d.key())) @ GplCompressionError::UnauthorizedTreeWriter
+    )]
     pub tree_config: Account<'info, TreeConfig>,
 
     #[account(mut)]
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
     pub merkle_tree: UncheckedAccount<'info>,
 
     // TODO: The seeds should be more descriptive
     #[account(seeds = [target_merkle_tree.key.as_ref()], bump)]
     pub target_tree_config: Account<'info, TreeConfig>,
 
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The proof against the merkle tree is checked by the compression program
     pub target_merkle_tree: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    // Signs for a program writing to a restricted tree as one of its delegates
+    pub delegate: Option<Signer<'info>>,
+
     pub compression_program: Program<'info, SplAccountCompression>,
     pub log_wrapper_program: Program<'info, Noop>,
     pub gpl_core_program: Program<'info, GplCore>,
@@ -64,16 +74,25 @@ pub fn create_compressed_comment_handler<'info>(
     reply_to: Pubkey,
     metadata_uri: String,
     random_hash: [u8; 32],
+    post_profile: Pubkey,
+    post_metadata_uri: String,
+    post_random_hash: [u8; 32],
+    post_reply_to: Option<Pubkey>,
     post_root: [u8; 32],
-    post_leaf: [u8; 32],
     post_index: u32,
 ) -> Result<()> {
     require!(metadata_uri.len() <= MAX_LEN_URI, PostError::URITooLong);
 
-    // Check if the reply_to exists
-    // FIXME:
-    // They can potentially pass any proof. How do we verify this belongs to the asset unless we
-    // construct the leaf ourselves?
+    // Rebuild the leaf of the reply_to so that the proof can only be for that post
+    let (post_id, post_leaf) = post_leaf_node(
+        ctx.accounts.target_merkle_tree.key,
+        post_profile,
+        post_metadata_uri,
+        post_random_hash,
+        post_reply_to,
+    )?;
+    require_keys_eq!(post_id, reply_to, GplCompressionError::InvalidTargetPost);
+
     verify_leaf(
         ctx.accounts.target_merkle_tree.key,
         ctx.bumps["target_tree_config"],
@@ -94,7 +113,7 @@ pub fn create_compressed_comment_handler<'info>(
 
     let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
 
-    let post = Post {
+    let post = CompressedPost {
         metadata_uri,
         random_hash,
         profile: *ctx.accounts.from_profile.to_account_info().key,
@@ -104,23 +123,27 @@ pub fn create_compressed_comment_handler<'info>(
     let leaf = LeafSchema {
         asset_id,
         seed_hash,
-        data_hash: hashv(&[&post.try_to_vec()?]).to_bytes(),
+        data_hash: post.data_hash()?,
     };
 
     let leaf_node = leaf.to_node()?;
 
     wrap_application_data_v1(leaf_node.to_vec(), &ctx.accounts.log_wrapper_program)?;
 
+    let index = get_rightmost_index(&ctx.accounts.merkle_tree)?;
+
     append_leaf(
         ctx.accounts.merkle_tree.key,
         ctx.bumps["tree_config"],
-        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.tree_config.to_account_info(),
         leaf_node,
         &ctx.accounts.merkle_tree,
         &ctx.accounts.compression_program,
         &ctx.accounts.log_wrapper_program,
     )?;
 
+    let root = get_root(&ctx.accounts.merkle_tree)?;
+
     emit!(CompressedCommentNew {
         asset_id,
         post_id,
@@ -130,7 +153,8 @@ pub fn create_compressed_comment_handler<'info>(
         random_hash: random_hash,
         metadata_uri: post.metadata_uri.clone(),
         timestamp: Clock::get()?.unix_timestamp,
-        index: 0 // TODO: Get the index from the tree
+        index,
+        root,
     });
     Ok(())
 }
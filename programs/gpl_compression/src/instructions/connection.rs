@@ -1,7 +1,9 @@
+use crate::errors::GplCompressionError;
 use crate::events::{CompressedConnectionDeleted, CompressedConnectionNew};
-use crate::state::TreeConfig;
-use crate::utils::{append_leaf, replace_leaf, try_find_asset_id, LeafSchema};
-use anchor_lang::Discriminator;
+use crate::state::{CompressedConnection, TreeConfig};
+use crate::utils::{
+    append_leaf, get_rightmost_index, get_root, replace_leaf, try_find_asset_id, LeafSchema,
+};
 use gpl_core::errors::ConnectionError;
 use spl_account_compression::wrap_application_data_v1;
 use spl_account_compression::Node;
@@ -20,6 +22,7 @@ use spl_account_compression::Noop;
 
 // Create Connection
 #[derive(Accounts)]
+#[instruction(kind: u8)]
 pub struct CreateCompressedConnection<'info> {
     #[account(
         seeds = [
@@ -33,16 +36,23 @@ pub struct CreateCompressedConnection<'info> {
     pub from_profile: Account<'info, Profile>,
     pub to_profile: Account<'info, Profile>,
 
-    #[account(seeds = [merkle_tree.key.as_ref()], bump)]
+    #[account(
+        seeds = [merkle_tree.key.as_ref()],
+        bump,
+        constraint = tree_config.can_write(&authority.key(), delegate.as_ref().map(|d| d.key())) @ GplCompressionError::UnauthorizedTreeWriter
+    )]
     pub tree_config: Account<'info, TreeConfig>,
 
     #[account(mut)]
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
     pub merkle_tree: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    // Signs for a program writing to a restricted tree as one of its delegates
+    pub delegate: Option<Signer<'info>>,
+
     pub compression_program: Program<'info, SplAccountCompression>,
     pub log_wrapper_program: Program<'info, Noop>,
     pub gpl_core_program: Program<'info, GplCore>,
@@ -52,6 +62,7 @@ pub struct CreateCompressedConnection<'info> {
 // Handler to create a new Connection
 pub fn create_compressed_connection_handler(
     ctx: Context<CreateCompressedConnection>,
+    kind: u8,
 ) -> Result<()> {
     let from_profile = &ctx.accounts.from_profile;
     let to_profile = &ctx.accounts.to_profile;
@@ -62,11 +73,19 @@ pub fn create_compressed_connection_handler(
         to_profile.key(),
         ConnectionError::CannotConnectToSelf
     );
+    Connection::validate_kind(kind)?;
+    // CHECK that the to_profile does not have to approve the connection
+    require!(
+        !Connection::requires_approval(kind, to_profile),
+        ConnectionError::ProfileIsPrivate
+    );
 
+    // The kind seed of a follow is empty, so neither the address nor the seed hash changes
     let connection_seeds = [
         CONNECTION_PREFIX_SEED.as_bytes(),
         from_profile.to_account_info().key.as_ref(),
         to_profile.to_account_info().key.as_ref(),
+        Connection::kind_seed(&kind),
     ];
 
     let (connection_id, connection_bump) =
@@ -76,7 +95,7 @@ pub fn create_compressed_connection_handler(
 
     let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
 
-    let connection = Connection {
+    let connection = CompressedConnection {
         from_profile: *from_profile.to_account_info().key,
         to_profile: *to_profile.to_account_info().key,
     };
@@ -84,32 +103,38 @@ pub fn create_compressed_connection_handler(
     let leaf = LeafSchema {
         asset_id,
         seed_hash,
-        data_hash: hashv(&[&Connection::DISCRIMINATOR, &connection.try_to_vec()?]).to_bytes(),
+        data_hash: connection.data_hash()?,
     };
 
     let leaf_node = leaf.to_node()?;
 
     wrap_application_data_v1(leaf_node.to_vec(), &ctx.accounts.log_wrapper_program)?;
 
+    let index = get_rightmost_index(&ctx.accounts.merkle_tree)?;
+
     append_leaf(
         ctx.accounts.merkle_tree.key,
         ctx.bumps["tree_config"],
-        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.tree_config.to_account_info(),
         leaf_node,
         &ctx.accounts.merkle_tree,
         &ctx.accounts.compression_program,
         &ctx.accounts.log_wrapper_program,
     )?;
 
+    let root = get_root(&ctx.accounts.merkle_tree)?;
+
     // emit a compressed connection event
     emit!(CompressedConnectionNew {
         connection_id,
         connection_bump,
         from_profile: *from_profile.to_account_info().key,
         to_profile: *to_profile.to_account_info().key,
+        kind,
         asset_id,
         timestamp: Clock::get()?.unix_timestamp,
-        index: 0 // TODO: Get the index from the tree
+        index,
+        root,
     });
 
     Ok(())
@@ -118,7 +143,7 @@ pub fn create_compressed_connection_handler(
 // Delete a Connection
 #[derive(Accounts)]
 // Ideally this should be compacted down to asset_id, root, index
-#[instruction(root: [u8;32], index: u32)]
+#[instruction(kind: u8, root: [u8;32], index: u32)]
 pub struct DeleteCompressedConnection<'info> {
     #[account(
         seeds = [
@@ -136,7 +161,7 @@ pub struct DeleteCompressedConnection<'info> {
     pub tree_config: Account<'info, TreeConfig>,
 
     #[account(mut)]
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
     pub merkle_tree: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -150,6 +175,7 @@ pub struct DeleteCompressedConnection<'info> {
 // Handler to delete a compressed Connection
 pub fn delete_compressed_connection_handler<'info>(
     ctx: Context<'_, '_, '_, 'info, DeleteCompressedConnection<'info>>,
+    kind: u8,
     root: [u8; 32],
     index: u32,
 ) -> Result<()> {
@@ -162,11 +188,14 @@ pub fn delete_compressed_connection_handler<'info>(
         to_profile.key(),
         ConnectionError::CannotConnectToSelf
     );
+    Connection::validate_kind(kind)?;
 
+    // The kind seed of a follow is empty, so neither the address nor the seed hash changes
     let connection_seeds = [
         CONNECTION_PREFIX_SEED.as_bytes(),
         from_profile.to_account_info().key.as_ref(),
         to_profile.to_account_info().key.as_ref(),
+        Connection::kind_seed(&kind),
     ];
 
     let (connection_id, connection_bump) =
@@ -176,7 +205,7 @@ pub fn delete_compressed_connection_handler<'info>(
 
     let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
 
-    let old_connection = Connection {
+    let old_connection = CompressedConnection {
         from_profile: *from_profile.to_account_info().key,
         to_profile: *to_profile.to_account_info().key,
     };
@@ -184,7 +213,7 @@ pub fn delete_compressed_connection_handler<'info>(
     let old_leaf = LeafSchema {
         asset_id,
         seed_hash,
-        data_hash: hashv(&[&Connection::DISCRIMINATOR, &old_connection.try_to_vec()?]).to_bytes(),
+        data_hash: old_connection.data_hash()?,
     };
 
     let old_leaf_node = old_leaf.to_node()?;
@@ -196,7 +225,7 @@ pub fn delete_compressed_connection_handler<'info>(
     replace_leaf(
         ctx.accounts.merkle_tree.key,
         ctx.bumps["tree_config"],
-        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.tree_config.to_account_info(),
         &ctx.accounts.merkle_tree,
         root,
         old_leaf_node,
@@ -213,6 +242,7 @@ pub fn delete_compressed_connection_handler<'info>(
         connection_bump,
         from_profile: *from_profile.to_account_info().key,
         to_profile: *to_profile.to_account_info().key,
+        kind,
         asset_id,
         timestamp: Clock::get()?.unix_timestamp,
         index
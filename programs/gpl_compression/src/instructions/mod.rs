@@ -1,9 +1,11 @@
+mod badge;
 mod comment;
 mod connection;
 mod post;
 mod reaction;
 mod tree_config;
 
+pub use badge::*;
 pub use comment::*;
 pub use connection::*;
 pub use post::*;
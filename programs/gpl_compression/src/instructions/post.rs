@@ -1,13 +1,15 @@
+use crate::errors::GplCompressionError;
 use crate::events::{CompressedPostDeleted, CompressedPostNew, CompressedPostUpdated};
-use crate::state::TreeConfig;
-use crate::utils::{append_leaf, replace_leaf, try_find_asset_id, LeafSchema};
-use anchor_lang::Discriminator;
+use crate::state::{CompressedPost, TreeConfig};
+use crate::utils::{
+    append_leaf, get_rightmost_index, get_root, replace_leaf, try_find_asset_id, LeafSchema,
+};
 
 use gpl_core::errors::PostError;
 use spl_account_compression::wrap_application_data_v1;
 use spl_account_compression::Node;
 
-use gpl_core::state::{Post, Profile, MAX_LEN_URI};
+use gpl_core::state::{Profile, MAX_LEN_URI};
 
 use anchor_lang::prelude::*;
 use std::convert::AsRef;
@@ -34,16 +36,23 @@ pub struct CreateCompressedPost<'info> {
     )]
     pub profile: Account<'info, Profile>,
 
-    #[account(seeds = [merkle_tree.key.as_ref()], bump)]
+    #[account(
+        seeds = [merkle_tree.key.as_ref()],
+        bump,
+        constraint = tree_config.can_write(&authority.key(), delegate.as_ref().map(|d| d.key())) @ GplCompressionError::UnauthorizedTreeWriter
+    )]
     pub tree_config: Account<'info, TreeConfig>,
 
     #[account(mut)]
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
     pub merkle_tree: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    // Signs for a program writing to a restricted tree as one of its delegates
+    pub delegate: Option<Signer<'info>>,
+
     pub compression_program: Program<'info, SplAccountCompression>,
     pub log_wrapper_program: Program<'info, Noop>,
     pub gpl_core_program: Program<'info, GplCore>,
@@ -67,7 +76,7 @@ pub fn create_compressed_post_handler(
 
     let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
 
-    let post = Post {
+    let post = CompressedPost {
         metadata_uri,
         random_hash,
         profile: *ctx.accounts.profile.to_account_info().key,
@@ -77,23 +86,27 @@ pub fn create_compressed_post_handler(
     let leaf = LeafSchema {
         asset_id,
         seed_hash,
-        data_hash: hashv(&[&Post::DISCRIMINATOR, &post.try_to_vec()?]).to_bytes(),
+        data_hash: post.data_hash()?,
     };
 
     let leaf_node = leaf.to_node()?;
 
     wrap_application_data_v1(leaf_node.to_vec(), &ctx.accounts.log_wrapper_program)?;
 
+    let index = get_rightmost_index(&ctx.accounts.merkle_tree)?;
+
     append_leaf(
         ctx.accounts.merkle_tree.key,
         ctx.bumps["tree_config"],
-        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.tree_config.to_account_info(),
         leaf_node,
         &ctx.accounts.merkle_tree,
         &ctx.accounts.compression_program,
         &ctx.accounts.log_wrapper_program,
     )?;
 
+    let root = get_root(&ctx.accounts.merkle_tree)?;
+
     emit!(CompressedPostNew {
         asset_id,
         post_id,
@@ -102,7 +115,8 @@ pub fn create_compressed_post_handler(
         random_hash: random_hash,
         metadata_uri: post.metadata_uri.clone(),
         timestamp: Clock::get()?.unix_timestamp,
-        index: 0 // TODO: Get the index from the tree
+        index,
+        root,
     });
 
     Ok(())
@@ -126,7 +140,7 @@ pub struct UpdateCompressedPost<'info> {
     pub tree_config: Account<'info, TreeConfig>,
 
     #[account(mut)]
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
     pub merkle_tree: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -159,7 +173,7 @@ pub fn update_compressed_post_handler<'info>(
 
     let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
 
-    let old_post = Post {
+    let old_post = CompressedPost {
         metadata_uri,
         random_hash,
         profile: *ctx.accounts.profile.to_account_info().key,
@@ -170,12 +184,12 @@ pub fn update_compressed_post_handler<'info>(
         asset_id,
         seed_hash,
         // May be better as a trait?
-        data_hash: hashv(&[&Post::DISCRIMINATOR, &old_post.try_to_vec()?]).to_bytes(),
+        data_hash: old_post.data_hash()?,
     };
 
     let old_leaf_node = old_leaf.to_node()?;
 
-    let new_post = Post {
+    let new_post = CompressedPost {
         metadata_uri: new_metadata_uri,
         random_hash,
         profile: *ctx.accounts.profile.to_account_info().key,
@@ -186,7 +200,7 @@ pub fn update_compressed_post_handler<'info>(
         asset_id,
         seed_hash,
         // May be better as a trait?
-        data_hash: hashv(&[&Post::DISCRIMINATOR, &new_post.try_to_vec()?]).to_bytes(),
+        data_hash: new_post.data_hash()?,
     };
 
     let new_leaf_node = new_leaf.to_node()?;
@@ -196,7 +210,7 @@ pub fn update_compressed_post_handler<'info>(
     replace_leaf(
         ctx.accounts.merkle_tree.key,
         ctx.bumps["tree_config"],
-        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.tree_config.to_account_info(),
         &ctx.accounts.merkle_tree,
         root,
         old_leaf_node,
@@ -242,7 +256,7 @@ pub struct DeleteCompressedPost<'info> {
     pub tree_config: Account<'info, TreeConfig>,
 
     #[account(mut)]
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
     pub merkle_tree: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -273,7 +287,7 @@ pub fn delete_compressed_post_handler<'info>(
 
     let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
 
-    let old_post = Post {
+    let old_post = CompressedPost {
         metadata_uri,
         random_hash,
         profile: *ctx.accounts.profile.to_account_info().key,
@@ -284,7 +298,7 @@ pub fn delete_compressed_post_handler<'info>(
         asset_id,
         seed_hash,
         // May be better as a trait?
-        data_hash: hashv(&[&Post::DISCRIMINATOR, &old_post.try_to_vec()?]).to_bytes(),
+        data_hash: old_post.data_hash()?,
     };
 
     let old_leaf_node = old_leaf.to_node()?;
@@ -296,7 +310,7 @@ pub fn delete_compressed_post_handler<'info>(
     replace_leaf(
         ctx.accounts.merkle_tree.key,
         ctx.bumps["tree_config"],
-        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.tree_config.to_account_info(),
         &ctx.accounts.merkle_tree,
         root,
         old_leaf_node,
@@ -1,5 +1,3 @@
-use anchor_lang::Discriminator;
-
 use gpl_core::state::Profile;
 use gpl_core::state::Reaction;
 
@@ -14,15 +12,18 @@ use spl_account_compression::wrap_application_data_v1;
 use spl_account_compression::Node;
 use spl_account_compression::Noop;
 
+use crate::errors::GplCompressionError;
 use crate::events::{CompressedReactionDeleted, CompressedReactionNew};
-use crate::state::TreeConfig;
+use crate::state::{CompressedReaction, TreeConfig};
 use crate::utils::verify_leaf;
 use crate::utils::LeafSchema;
-use crate::utils::{append_leaf, replace_leaf, try_find_asset_id};
+use crate::utils::{
+    append_leaf, get_rightmost_index, get_root, post_leaf_node, replace_leaf, try_find_asset_id,
+};
 
 // Create Reaction
 #[derive(Accounts)]
-#[instruction(to_post: Pubkey, reaction_type: String, post_root: [u8; 32], post_leaf: [u8; 32], post_index: u32)]
+#[instruction(to_post: Pubkey, reaction_type: String, post_profile: Pubkey, post_metadata_uri: String, post_random_hash: [u8; 32], post_reply_to: Option<Pubkey>, post_root: [u8; 32], post_index: u32)]
 pub struct CreateCompressedReaction<'info> {
     #[account(
         seeds = [
@@ -35,23 +36,30 @@ pub struct CreateCompressedReaction<'info> {
     )]
     pub from_profile: Account<'info, Profile>,
 
-    #[account(seeds = [merkle_tree.key.as_ref()], bump)]
+    #[account(
+        seeds = [merkle_tree.key.as_ref()],
+        bump,
+        constraint = tree_config.can_write(&authority.key(), delegate.as_ref().map(|d| d.key())) @ GplCompressionError::UnauthorizedTreeWriter
+    )]
     pub tree_config: Account<'info, TreeConfig>,
 
     #[account(mut)]
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
     pub merkle_tree: UncheckedAccount<'info>,
 
     // TODO: The seeds should be more descriptive
     #[account(seeds = [target_merkle_tree.key.as_ref()], bump)]
     pub target_tree_config: Account<'info, TreeConfig>,
 
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The proof against the merkle tree is checked by the compression program
     pub target_merkle_tree: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    // Signs for a program writing to a restricted tree as one of its delegates
+    pub delegate: Option<Signer<'info>>,
+
     pub compression_program: Program<'info, SplAccountCompression>,
     pub log_wrapper_program: Program<'info, Noop>,
     pub gpl_core_program: Program<'info, GplCore>,
@@ -63,18 +71,27 @@ pub fn create_compressed_reaction_handler<'info>(
     ctx: Context<'_, '_, '_, 'info, CreateCompressedReaction<'info>>,
     to_post: Pubkey,
     reaction_type: String,
+    post_profile: Pubkey,
+    post_metadata_uri: String,
+    post_random_hash: [u8; 32],
+    post_reply_to: Option<Pubkey>,
     post_root: [u8; 32],
-    post_leaf: [u8; 32],
     post_index: u32,
 ) -> Result<()> {
     Reaction::validate_reaction_type(&reaction_type)?;
 
     let from_profile = &ctx.accounts.from_profile;
 
-    // Check if the to_post exists
-    // FIXME:
-    // They can potentially pass any proof. How do we verify this belongs to the asset unless we
-    // construct the leaf ourselves?
+    // Rebuild the leaf of the to_post so that the proof can only be for that post
+    let (post_id, post_leaf) = post_leaf_node(
+        ctx.accounts.target_merkle_tree.key,
+        post_profile,
+        post_metadata_uri,
+        post_random_hash,
+        post_reply_to,
+    )?;
+    require_keys_eq!(post_id, to_post, GplCompressionError::InvalidTargetPost);
+
     verify_leaf(
         ctx.accounts.target_merkle_tree.key,
         ctx.bumps["target_tree_config"],
@@ -100,7 +117,7 @@ pub fn create_compressed_reaction_handler<'info>(
 
     let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
 
-    let reaction = Reaction {
+    let reaction = CompressedReaction {
         from_profile: *from_profile.to_account_info().key,
         to_post,
         reaction_type: reaction_type.clone(),
@@ -109,23 +126,27 @@ pub fn create_compressed_reaction_handler<'info>(
     let leaf = LeafSchema {
         asset_id,
         seed_hash,
-        data_hash: hashv(&[&Reaction::DISCRIMINATOR, &reaction.try_to_vec()?]).to_bytes(),
+        data_hash: reaction.data_hash()?,
     };
 
     let leaf_node = leaf.to_node()?;
 
     wrap_application_data_v1(leaf_node.to_vec(), &ctx.accounts.log_wrapper_program)?;
 
+    let index = get_rightmost_index(&ctx.accounts.merkle_tree)?;
+
     append_leaf(
         ctx.accounts.merkle_tree.key,
         ctx.bumps["tree_config"],
-        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.tree_config.to_account_info(),
         leaf_node,
         &ctx.accounts.merkle_tree,
         &ctx.accounts.compression_program,
         &ctx.accounts.log_wrapper_program,
     )?;
 
+    let root = get_root(&ctx.accounts.merkle_tree)?;
+
     // emit a compressed reaction event
     emit!(CompressedReactionNew {
         from_profile: *from_profile.to_account_info().key,
@@ -134,7 +155,8 @@ pub fn create_compressed_reaction_handler<'info>(
         reaction_id,
         reaction_bump,
         asset_id,
-        index: 0, //TODO: get the index
+        index,
+        root,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
@@ -160,7 +182,7 @@ pub struct DeleteCompressedReaction<'info> {
     pub tree_config: Account<'info, TreeConfig>,
 
     #[account(mut)]
-    /// CHECK The account must have the same authority as that of the config
+    /// CHECK The tree config is the authority of the merkle tree, checked by the compression program
     pub merkle_tree: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -197,7 +219,7 @@ pub fn delete_compressed_reaction_handler<'info>(
 
     let asset_id = try_find_asset_id(ctx.accounts.merkle_tree.key, seed_hash)?;
 
-    let old_reaction = Reaction {
+    let old_reaction = CompressedReaction {
         from_profile: *from_profile.to_account_info().key,
         to_post,
         reaction_type: reaction_type.clone(),
@@ -206,7 +228,7 @@ pub fn delete_compressed_reaction_handler<'info>(
     let old_leaf = LeafSchema {
         asset_id,
         seed_hash,
-        data_hash: hashv(&[&Reaction::DISCRIMINATOR, &old_reaction.try_to_vec()?]).to_bytes(),
+        data_hash: old_reaction.data_hash()?,
     };
 
     let old_leaf_node = old_leaf.to_node()?;
@@ -218,7 +240,7 @@ pub fn delete_compressed_reaction_handler<'info>(
     replace_leaf(
         ctx.accounts.merkle_tree.key,
         ctx.bumps["tree_config"],
-        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.tree_config.to_account_info(),
         &ctx.accounts.merkle_tree,
         root,
         old_leaf_node,
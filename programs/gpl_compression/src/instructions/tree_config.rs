@@ -1,5 +1,8 @@
-use crate::state::TreeConfig;
+use crate::errors::GplCompressionError;
+use crate::state::{TreeConfig, TreeMode};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
 use spl_account_compression::program::SplAccountCompression;
 use spl_account_compression::Noop;
 
@@ -40,12 +43,15 @@ pub fn initialize_tree_handler(
     tree_config.set_inner(TreeConfig {
         authority: ctx.accounts.authority.key(),
         merkle_tree: merkle_tree.key(),
+        mode: TreeMode::Restricted,
+        delegates: vec![],
     });
     let authority_pda_signer = &[&seeds[..]];
+    // The tree config owns the merkle tree so that writes can only go through the program
     let cpi_ctx = CpiContext::new_with_signer(
         ctx.accounts.compression_program.to_account_info(),
         spl_account_compression::cpi::accounts::Initialize {
-            authority: ctx.accounts.authority.to_account_info(),
+            authority: tree_config.to_account_info(),
             merkle_tree,
             noop: ctx.accounts.log_wrapper.to_account_info(),
         },
@@ -53,3 +59,107 @@ pub fn initialize_tree_handler(
     );
     spl_account_compression::cpi::init_empty_merkle_tree(cpi_ctx, max_depth, max_buffer_size)
 }
+
+// Update TreeConfig
+#[derive(Accounts)]
+pub struct UpdateTreeConfig<'info> {
+    #[account(
+        mut,
+        seeds = [tree_config.merkle_tree.as_ref()],
+        bump,
+        has_one = authority,
+    )]
+    pub tree_config: Account<'info, TreeConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+// Handler for UpdateTreeConfig
+pub fn update_tree_config_handler(
+    ctx: Context<UpdateTreeConfig>,
+    mode: TreeMode,
+    delegates: Vec<Pubkey>,
+) -> Result<()> {
+    TreeConfig::validate_delegates(&delegates)?;
+    let tree_config = &mut ctx.accounts.tree_config;
+    tree_config.mode = mode;
+    tree_config.delegates = delegates;
+    Ok(())
+}
+
+// Migrate a TreeConfig created before the tree modes
+#[derive(Accounts)]
+pub struct MigrateTreeConfig<'info> {
+    #[account(
+        mut,
+        seeds = [merkle_tree.key.as_ref()],
+        bump,
+        owner = crate::ID,
+        constraint = tree_config.data_len() == TreeConfig::LEGACY_LEN @ GplCompressionError::InvalidLegacyTreeConfig,
+    )]
+    /// CHECK: The legacy layout is checked in the handler
+    pub tree_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The authority of the tree is checked by the compression program
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler for MigrateTreeConfig
+pub fn migrate_tree_config_handler(ctx: Context<MigrateTreeConfig>) -> Result<()> {
+    let tree_config = ctx.accounts.tree_config.to_account_info();
+    let authority = {
+        let data = tree_config.try_borrow_data()?;
+        require!(
+            data[..8] == TreeConfig::DISCRIMINATOR,
+            GplCompressionError::InvalidLegacyTreeConfig
+        );
+        Pubkey::try_from_slice(&data[8..40])?
+    };
+    require_keys_eq!(
+        authority,
+        ctx.accounts.authority.key(),
+        ErrorCode::ConstraintHasOne
+    );
+
+    // The authority pays for the space of the mode and the delegates
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(TreeConfig::LEN)
+        .saturating_sub(tree_config.lamports());
+    if rent_shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: tree_config.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    tree_config.realloc(TreeConfig::LEN, true)?;
+    TreeConfig {
+        authority,
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        mode: TreeMode::Restricted,
+        delegates: vec![],
+    }
+    .try_serialize(&mut &mut tree_config.try_borrow_mut_data()?[..])?;
+
+    // Legacy trees are owned by the wallet of the authority, writes now go through the tree config
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.compression_program.to_account_info(),
+        spl_account_compression::cpi::accounts::TransferAuthority {
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        },
+    );
+    spl_account_compression::cpi::transfer_authority(cpi_ctx, tree_config.key())
+}
@@ -9,6 +9,7 @@ mod utils;
 
 use crate::errors::GplCompressionError;
 use crate::instructions::*;
+use crate::state::TreeMode;
 
 declare_id!("41kNwkQ9jESNYZJyAA1ENscQfx7vfkEf6uetVSFmfyaW");
 
@@ -35,6 +36,20 @@ pub mod gpl_compression {
         initialize_tree_handler(ctx, max_depth, max_buffer_size)
     }
 
+    // update the mode and the delegates of a tree
+    pub fn update_tree_config(
+        ctx: Context<UpdateTreeConfig>,
+        mode: TreeMode,
+        delegates: Vec<Pubkey>,
+    ) -> Result<()> {
+        update_tree_config_handler(ctx, mode, delegates)
+    }
+
+    // migrate a tree config created before the tree modes
+    pub fn migrate_tree_config(ctx: Context<MigrateTreeConfig>) -> Result<()> {
+        migrate_tree_config_handler(ctx)
+    }
+
     // create a compressed post
     pub fn create_compressed_post(
         ctx: Context<CreateCompressedPost>,
@@ -75,17 +90,21 @@ pub mod gpl_compression {
     }
 
     // create a compressed connection
-    pub fn create_compressed_connection(ctx: Context<CreateCompressedConnection>) -> Result<()> {
-        create_compressed_connection_handler(ctx)
+    pub fn create_compressed_connection(
+        ctx: Context<CreateCompressedConnection>,
+        kind: u8,
+    ) -> Result<()> {
+        create_compressed_connection_handler(ctx, kind)
     }
 
     // delete a compressed connection
     pub fn delete_compressed_connection<'info>(
         ctx: Context<'_, '_, '_, 'info, DeleteCompressedConnection<'info>>,
+        kind: u8,
         root: [u8; 32],
         index: u32,
     ) -> Result<()> {
-        delete_compressed_connection_handler(ctx, root, index)
+        delete_compressed_connection_handler(ctx, kind, root, index)
     }
 
     // create a compressed reaction
@@ -93,16 +112,22 @@ pub mod gpl_compression {
         ctx: Context<'_, '_, '_, 'info, CreateCompressedReaction<'info>>,
         to_post: Pubkey,
         reaction_type: String,
+        post_profile: Pubkey,
+        post_metadata_uri: String,
+        post_random_hash: [u8; 32],
+        post_reply_to: Option<Pubkey>,
         post_root: [u8; 32],
-        post_leaf: [u8; 32],
         post_index: u32,
     ) -> Result<()> {
         create_compressed_reaction_handler(
             ctx,
             to_post,
             reaction_type,
+            post_profile,
+            post_metadata_uri,
+            post_random_hash,
+            post_reply_to,
             post_root,
-            post_leaf,
             post_index,
         )
     }
@@ -124,8 +149,11 @@ pub mod gpl_compression {
         reply_to: Pubkey,
         metadata_uri: String,
         random_hash: [u8; 32],
+        post_profile: Pubkey,
+        post_metadata_uri: String,
+        post_random_hash: [u8; 32],
+        post_reply_to: Option<Pubkey>,
         post_root: [u8; 32],
-        post_leaf: [u8; 32],
         post_index: u32,
     ) -> Result<()> {
         create_compressed_comment_handler(
@@ -133,9 +161,80 @@ pub mod gpl_compression {
             reply_to,
             metadata_uri,
             random_hash,
+            post_profile,
+            post_metadata_uri,
+            post_random_hash,
+            post_reply_to,
             post_root,
-            post_leaf,
             post_index,
         )
     }
+
+    // create a compressed badge
+    pub fn create_compressed_badge(
+        ctx: Context<CreateCompressedBadge>,
+        metadata_uri: String,
+        expires_at: Option<i64>,
+        attributes: Vec<u8>,
+    ) -> Result<()> {
+        create_compressed_badge_handler(ctx, metadata_uri, expires_at, attributes)
+    }
+
+    // update a compressed badge
+    pub fn update_compressed_badge<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateCompressedBadge<'info>>,
+        metadata_uri: String,
+        new_metadata_uri: String,
+        expires_at: Option<i64>,
+        attributes: Vec<u8>,
+        new_attributes: Vec<u8>,
+        pending: bool,
+        root: [u8; 32],
+        index: u32,
+    ) -> Result<()> {
+        update_compressed_badge_handler(
+            ctx,
+            metadata_uri,
+            new_metadata_uri,
+            expires_at,
+            attributes,
+            new_attributes,
+            pending,
+            root,
+            index,
+        )
+    }
+
+    // accept a pending compressed badge as its holder
+    pub fn accept_compressed_badge<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptCompressedBadge<'info>>,
+        metadata_uri: String,
+        expires_at: Option<i64>,
+        attributes: Vec<u8>,
+        root: [u8; 32],
+        index: u32,
+    ) -> Result<()> {
+        accept_compressed_badge_handler(ctx, metadata_uri, expires_at, attributes, root, index)
+    }
+
+    // burn a compressed badge
+    pub fn burn_compressed_badge<'info>(
+        ctx: Context<'_, '_, '_, 'info, BurnCompressedBadge<'info>>,
+        metadata_uri: String,
+        expires_at: Option<i64>,
+        attributes: Vec<u8>,
+        pending: bool,
+        root: [u8; 32],
+        index: u32,
+    ) -> Result<()> {
+        burn_compressed_badge_handler(
+            ctx,
+            metadata_uri,
+            expires_at,
+            attributes,
+            pending,
+            root,
+            index,
+        )
+    }
 }
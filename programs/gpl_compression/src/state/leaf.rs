@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+use anchor_lang::Discriminator;
+use gpl_core::state::{Badge, Connection, Post, Reaction};
+
+// The data hashed into the leaves of compressed accounts
+// These layouts are frozen, fields added to the gpl_core accounts must not change the hash
+// of leaves that are already in a tree
+
+// The layout of a gpl_core Post before posts could be gated
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedPost {
+    pub profile: Pubkey,
+    pub metadata_uri: String,
+    pub random_hash: [u8; 32],
+    pub reply_to: Option<Pubkey>,
+}
+
+impl CompressedPost {
+    // Comments have been hashed without the discriminator since they were added, the leaves of
+    // existing comments depend on it
+    pub fn data_hash(&self) -> Result<[u8; 32]> {
+        let data = self.try_to_vec()?;
+        if self.reply_to.is_some() {
+            Ok(hashv(&[&data]).to_bytes())
+        } else {
+            Ok(hashv(&[&Post::DISCRIMINATOR, &data]).to_bytes())
+        }
+    }
+}
+
+// The layout of a gpl_core Connection before connections had a kind
+// The kind of a compressed connection is part of its seeds instead
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedConnection {
+    pub from_profile: Pubkey,
+    pub to_profile: Pubkey,
+}
+
+impl CompressedConnection {
+    pub fn data_hash(&self) -> Result<[u8; 32]> {
+        Ok(hashv(&[&Connection::DISCRIMINATOR, &self.try_to_vec()?]).to_bytes())
+    }
+}
+
+// The layout of a gpl_core Reaction before reactions recorded whether they were counted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedReaction {
+    pub from_profile: Pubkey,
+    pub to_post: Pubkey,
+    pub reaction_type: String,
+}
+
+impl CompressedReaction {
+    pub fn data_hash(&self) -> Result<[u8; 32]> {
+        Ok(hashv(&[&Reaction::DISCRIMINATOR, &self.try_to_vec()?]).to_bytes())
+    }
+}
+
+// The layout of a gpl_core Badge when badges were compressed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedBadge {
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub update_authority: Pubkey,
+    pub schema: Pubkey,
+    pub metadata_uri: String,
+    pub expires_at: Option<i64>,
+    pub revoked: bool,
+    pub attributes: Vec<u8>,
+    pub pending: bool,
+}
+
+impl CompressedBadge {
+    pub fn data_hash(&self) -> Result<[u8; 32]> {
+        Ok(hashv(&[&Badge::DISCRIMINATOR, &self.try_to_vec()?]).to_bytes())
+    }
+}
@@ -1,13 +1,53 @@
+use crate::errors::GplCompressionError;
 use anchor_lang::prelude::*;
 use std::mem::size_of;
 
+mod leaf;
+pub use leaf::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TreeMode {
+    // Anyone can append to the tree
+    Public,
+    // Only the authority and the delegates can append to the tree
+    Restricted,
+}
+
 // Account to hold the compressed data in a tree
+// The tree config is the authority of the merkle tree, writes go through the program
 #[account]
 pub struct TreeConfig {
     pub authority: Pubkey,
     pub merkle_tree: Pubkey,
+    pub mode: TreeMode,
+    // Keys allowed to append to a restricted tree, programs delegate with one of their PDAs
+    pub delegates: Vec<Pubkey>,
 }
 
 impl TreeConfig {
-    pub const LEN: usize = 8 + size_of::<Self>();
+    pub const MAX_DELEGATES: usize = 10;
+
+    pub const LEN: usize = 8 + size_of::<Self>() + Self::MAX_DELEGATES * size_of::<Pubkey>();
+
+    // Size of a tree config created before the tree modes, with only the authority and the tree
+    pub const LEGACY_LEN: usize = 8 + 32 + 32;
+
+    pub fn validate_delegates(delegates: &[Pubkey]) -> Result<()> {
+        require!(
+            delegates.len() <= Self::MAX_DELEGATES,
+            GplCompressionError::TooManyDelegates
+        );
+        Ok(())
+    }
+
+    // The delegate is a separate signer, the authority of a profile can't be a program PDA
+    pub fn can_write(&self, authority: &Pubkey, delegate: Option<Pubkey>) -> bool {
+        match self.mode {
+            TreeMode::Public => true,
+            TreeMode::Restricted => {
+                self.authority == *authority
+                    || matches!(delegate, Some(delegate) if self.delegates.contains(&delegate))
+            }
+        }
+    }
 }
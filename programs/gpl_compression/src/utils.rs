@@ -1,9 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::keccak::hashv;
 use borsh::{BorshDeserialize, BorshSerialize};
+use gpl_core::constants::POST_PREFIX_SEED;
+use gpl_core::program::GplCore;
 
+use spl_account_compression::state::{
+    merkle_tree_get_size, ConcurrentMerkleTreeHeader, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
 use spl_account_compression::Node;
 
+use crate::state::CompressedPost;
 use crate::GplCompressionError;
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
@@ -47,6 +53,88 @@ pub fn try_find_asset_id(merkle_tree: &Pubkey, seed_hash: [u8; 32]) -> Result<Pu
     }
 }
 
+// Rebuild the leaf of a compressed post from its fields
+// Returns the post id along with the leaf so that callers can bind proofs to a post
+pub fn post_leaf_node(
+    merkle_tree: &Pubkey,
+    profile: Pubkey,
+    metadata_uri: String,
+    random_hash: [u8; 32],
+    reply_to: Option<Pubkey>,
+) -> Result<(Pubkey, Node)> {
+    let post_seeds = [POST_PREFIX_SEED.as_bytes(), random_hash.as_ref()];
+
+    let (post_id, _) = Pubkey::find_program_address(&post_seeds, &GplCore::id());
+
+    let seed_hash = hashv(&post_seeds).to_bytes();
+
+    let asset_id = try_find_asset_id(merkle_tree, seed_hash)?;
+
+    let post = CompressedPost {
+        metadata_uri,
+        random_hash,
+        profile,
+        reply_to,
+    };
+
+    let leaf = LeafSchema {
+        asset_id,
+        seed_hash,
+        data_hash: post.data_hash()?,
+    };
+
+    Ok((post_id, leaf.to_node()?))
+}
+
+// The tree follows the header as a #[repr(C)] ConcurrentMerkleTree<MAX_DEPTH, MAX_BUFFER_SIZE>:
+// sequence_number, active_index and buffer_size (u64 each), the change logs and the rightmost proof
+const TREE_COUNTERS_SIZE: usize = 24;
+
+fn read_tree_header(data: &[u8]) -> Result<(ConcurrentMerkleTreeHeader, usize)> {
+    let header_bytes = data
+        .get(..CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1)
+        .ok_or(GplCompressionError::InvalidMerkleTree)?;
+    let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+    header.assert_valid()?;
+    let merkle_tree_size = merkle_tree_get_size(&header)?;
+    require!(
+        data.len() >= CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 + merkle_tree_size,
+        GplCompressionError::InvalidMerkleTree
+    );
+    Ok((header, merkle_tree_size))
+}
+
+// Index of the next leaf to be appended, read before append_leaf
+pub fn get_rightmost_index(merkle_tree_account: &AccountInfo) -> Result<u32> {
+    let data = merkle_tree_account.try_borrow_data()?;
+    let (_, merkle_tree_size) = read_tree_header(&data)?;
+    // The rightmost proof ends with its index (u32) and padding (u32)
+    let offset = CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 + merkle_tree_size - 8;
+    let mut index = [0u8; 4];
+    index.copy_from_slice(&data[offset..offset + 4]);
+    Ok(u32::from_le_bytes(index))
+}
+
+// Current root of the tree, the root of the active change log
+pub fn get_root(merkle_tree_account: &AccountInfo) -> Result<[u8; 32]> {
+    let data = merkle_tree_account.try_borrow_data()?;
+    let (header, _) = read_tree_header(&data)?;
+    let tree_offset = CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1;
+    let mut active_index = [0u8; 8];
+    active_index.copy_from_slice(&data[tree_offset + 8..tree_offset + 16]);
+    let active_index = u64::from_le_bytes(active_index) as usize;
+    require!(
+        active_index < header.get_max_buffer_size() as usize,
+        GplCompressionError::InvalidMerkleTree
+    );
+    // Each change log holds the root, the path (max_depth nodes), the index and padding
+    let change_log_size = 32 * (header.get_max_depth() as usize + 1) + 8;
+    let offset = tree_offset + TREE_COUNTERS_SIZE + active_index * change_log_size;
+    let mut root = [0u8; 32];
+    root.copy_from_slice(&data[offset..offset + 32]);
+    Ok(root)
+}
+
 pub fn replace_leaf<'info>(
     merkle_tree: &Pubkey,
     bump: u8,
@@ -4,3 +4,7 @@ pub const PROFILE_METADATA_PREFIX_SEED: &str = "profile_metadata";
 pub const POST_PREFIX_SEED: &str = "post";
 pub const CONNECTION_PREFIX_SEED: &str = "connection";
 pub const REACTION_PREFIX_SEED: &str = "reaction";
+pub const BLOCK_PREFIX_SEED: &str = "block";
+pub const BINARY_REACTION_PREFIX_SEED: &str = "binary_reaction";
+pub const REACTION_SET_PREFIX_SEED: &str = "reaction_set";
+pub const REACTION_TALLY_PREFIX_SEED: &str = "reaction_tally";
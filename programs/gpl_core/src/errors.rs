@@ -8,11 +8,38 @@ pub enum GumError {
     UnverifiedIssuer,
     InvalidSignerToVerify,
     ReactionTypeTooLong,
+    AccountNotOrphaned,
+    CannotBlockSelf,
+    BlockedByProfile,
+    TooManyReactionTypes,
+    ReactionTypeNotAllowed,
+    InvalidTipAmount,
+    InvalidTokenAccount,
+    InvalidBadgeExpiry,
+    BadgeRevoked,
+    SchemaNotOwnedByIssuer,
+    InvalidSchemaFields,
+    InvalidBadgeAttributes,
+    SuspendedIssuer,
+    BadgeNotPending,
+    BadgeNotTransferable,
+    BadgePending,
+    BadgeExpired,
+    InvalidBadge,
+    IssuerNotSuspended,
+    InvalidLegacySchema,
 }
 
 #[error_code]
 pub enum PostError {
     URITooLong,
+    TooManyMentions,
+    DuplicateMention,
+    InvalidMention,
+    TooManyTags,
+    DuplicateTag,
+    InvalidGate,
+    AccessDenied,
 }
 
 #[error_code]
@@ -23,4 +50,20 @@ pub enum ProfileMetadataError {
 #[error_code]
 pub enum ConnectionError {
     CannotConnectToSelf,
+    InvalidConnectionKind,
+    ProfileIsPrivate,
+    ApprovalNotRequired,
+    ConnectionNotPending,
+    InvalidConnectionBatch,
+    ConnectionBatchTooLarge,
+    InvalidLegacyConnection,
+}
+
+#[error_code]
+pub enum ProtocolConfigError {
+    UnauthorizedInitializer,
+    InvalidAdmins,
+    InvalidThreshold,
+    TooManyVerifiers,
+    NotEnoughAdminSigners,
 }
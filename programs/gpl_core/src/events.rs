@@ -1,3 +1,4 @@
+use crate::state::{PostGate, SchemaField};
 use anchor_lang::prelude::*;
 
 // This event is emitted whenever a new profile is created.
@@ -20,6 +21,14 @@ pub struct ProfileUpdated {
     pub metadata_uri: String,
 }
 
+// This event is emitted whenever a profile is made private or public.
+#[event]
+pub struct ProfilePrivacyUpdated {
+    pub profile: Pubkey,
+    pub private: bool,
+    pub timestamp: i64,
+}
+
 // This event is emitted whenever a profile is deleted.
 #[event]
 pub struct ProfileDeleted {
@@ -36,6 +45,9 @@ pub struct PostNew {
     pub profile: Pubkey,
     pub random_hash: [u8; 32],
     pub metadata_uri: String,
+    pub mentions: Vec<Pubkey>,
+    pub tags: Vec<[u8; 32]>,
+    pub gate: Option<PostGate>,
     pub timestamp: i64,
 }
 
@@ -56,6 +68,18 @@ pub struct PostDeleted {
     pub timestamp: i64,
 }
 
+// This event is emitted whenever a post is tipped.
+// The mint is None when the tip is paid in lamports.
+#[event]
+pub struct PostTipped {
+    pub post: Pubkey,
+    pub from_profile: Pubkey,
+    pub to_profile: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
 // This event is emitted whenever a new comment is created.
 #[event]
 pub struct PostCommentNew {
@@ -64,6 +88,8 @@ pub struct PostCommentNew {
     pub random_hash: [u8; 32],
     pub metadata_uri: String,
     pub reply_to: Pubkey,
+    pub mentions: Vec<Pubkey>,
+    pub tags: Vec<[u8; 32]>,
     pub timestamp: i64,
 }
 
@@ -73,6 +99,27 @@ pub struct ConnectionNew {
     pub connection: Pubkey,
     pub from_profile: Pubkey,
     pub to_profile: Pubkey,
+    pub kind: u8,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a connection to a private profile is requested.
+#[event]
+pub struct ConnectionRequested {
+    pub connection: Pubkey,
+    pub from_profile: Pubkey,
+    pub to_profile: Pubkey,
+    pub kind: u8,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a connection request is rejected.
+#[event]
+pub struct ConnectionRejected {
+    pub connection: Pubkey,
+    pub from_profile: Pubkey,
+    pub to_profile: Pubkey,
+    pub kind: u8,
     pub timestamp: i64,
 }
 
@@ -82,6 +129,25 @@ pub struct ConnectionDeleted {
     pub connection: Pubkey,
     pub from_profile: Pubkey,
     pub to_profile: Pubkey,
+    pub kind: u8,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a new block is created.
+#[event]
+pub struct BlockNew {
+    pub block: Pubkey,
+    pub blocker: Pubkey,
+    pub blocked: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a block is deleted.
+#[event]
+pub struct BlockDeleted {
+    pub block: Pubkey,
+    pub blocker: Pubkey,
+    pub blocked: Pubkey,
     pub timestamp: i64,
 }
 
@@ -95,6 +161,39 @@ pub struct ReactionNew {
     pub timestamp: i64,
 }
 
+// This event is emitted whenever a new binary reaction is created.
+#[event]
+pub struct BinaryReactionNew {
+    pub reaction: Pubkey,
+    pub reaction_type: [u8; 32],
+    pub from_profile: Pubkey,
+    pub to_post: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a binary reaction is deleted.
+#[event]
+pub struct BinaryReactionDeleted {
+    pub reaction: Pubkey,
+    pub reaction_type: [u8; 32],
+    pub from_profile: Pubkey,
+    pub to_post: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a reaction is updated.
+// The reaction type is part of the seeds, so the updated reaction lives in a new account.
+#[event]
+pub struct ReactionUpdated {
+    pub reaction: Pubkey,
+    pub old_reaction: Pubkey,
+    pub reaction_type: String,
+    pub old_reaction_type: String,
+    pub from_profile: Pubkey,
+    pub to_post: Pubkey,
+    pub timestamp: i64,
+}
+
 // This event is emitted whenever a reaction is deleted.
 #[event]
 pub struct ReactionDeleted {
@@ -104,3 +203,161 @@ pub struct ReactionDeleted {
     pub to_post: Pubkey,
     pub timestamp: i64,
 }
+
+// This event is emitted whenever a new badge is created.
+#[event]
+pub struct BadgeNew {
+    pub badge: Pubkey,
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub schema: Pubkey,
+    pub update_authority: Pubkey,
+    pub metadata_uri: String,
+    pub expires_at: Option<i64>,
+    pub attributes: Vec<u8>,
+    pub pending: bool,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a holder accepts a pending badge.
+#[event]
+pub struct BadgeAccepted {
+    pub badge: Pubkey,
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub schema: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a badge is transferred to another profile.
+#[event]
+pub struct BadgeTransferred {
+    pub badge: Pubkey,
+    pub old_badge: Pubkey,
+    pub issuer: Pubkey,
+    pub schema: Pubkey,
+    pub from_holder: Pubkey,
+    pub to_holder: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a badge is updated.
+#[event]
+pub struct BadgeUpdated {
+    pub badge: Pubkey,
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub schema: Pubkey,
+    pub metadata_uri: String,
+    pub attributes: Vec<u8>,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a badge is burned.
+#[event]
+pub struct BadgeBurned {
+    pub badge: Pubkey,
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub schema: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a badge is revoked.
+#[event]
+pub struct BadgeRevoked {
+    pub badge: Pubkey,
+    pub issuer: Pubkey,
+    pub holder: Pubkey,
+    pub schema: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a new issuer is created.
+#[event]
+pub struct IssuerNew {
+    pub issuer: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever an issuer is verified.
+#[event]
+pub struct IssuerVerified {
+    pub issuer: Pubkey,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever an issuer is unverified.
+#[event]
+pub struct IssuerUnverified {
+    pub issuer: Pubkey,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever an issuer is suspended.
+#[event]
+pub struct IssuerSuspended {
+    pub issuer: Pubkey,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever the suspension of an issuer is lifted.
+#[event]
+pub struct IssuerUnsuspended {
+    pub issuer: Pubkey,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever an issuer is deleted.
+#[event]
+pub struct IssuerDeleted {
+    pub issuer: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a new schema is created.
+#[event]
+pub struct SchemaNew {
+    pub schema: Pubkey,
+    pub authority: Pubkey,
+    pub metadata_uri: String,
+    pub random_hash: [u8; 32],
+    pub official: bool,
+    pub fields: Vec<SchemaField>,
+    pub requires_acceptance: bool,
+    pub transferable: bool,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a schema is updated.
+#[event]
+pub struct SchemaUpdated {
+    pub schema: Pubkey,
+    pub authority: Pubkey,
+    pub metadata_uri: String,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever a schema is deleted.
+#[event]
+pub struct SchemaDeleted {
+    pub schema: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+// This event is emitted whenever the protocol config is initialized or updated.
+#[event]
+pub struct ProtocolConfigUpdated {
+    pub protocol_config: Pubkey,
+    pub admins: Vec<Pubkey>,
+    pub threshold: u8,
+    pub verifiers: Vec<Pubkey>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,27 @@
+use crate::cpi::accounts::AssertBadge;
+use crate::errors::GumError;
+use crate::state::Badge;
+use anchor_lang::prelude::*;
+
+// Assert that the holder profile has an active badge from the issuer under the schema
+// The badge account is checked against its PDA before the CPI into gpl_core
+pub fn assert_badge<'info>(
+    gpl_core_program: AccountInfo<'info>,
+    badge: AccountInfo<'info>,
+    issuer: AccountInfo<'info>,
+    schema: AccountInfo<'info>,
+    holder: AccountInfo<'info>,
+) -> Result<()> {
+    let (badge_pda, _) = Badge::find_pda(issuer.key, schema.key, holder.key);
+    require_keys_eq!(badge.key(), badge_pda, GumError::InvalidBadge);
+
+    crate::cpi::assert_badge(CpiContext::new(
+        gpl_core_program,
+        AssertBadge {
+            badge,
+            issuer,
+            schema,
+            holder,
+        },
+    ))
+}
@@ -1,10 +1,15 @@
 use crate::constants::*;
 use crate::errors::GumError;
+use crate::events::{
+    BadgeAccepted, BadgeBurned, BadgeNew, BadgeRevoked, BadgeTransferred, BadgeUpdated,
+    IssuerDeleted, IssuerNew, IssuerSuspended, IssuerUnsuspended, IssuerUnverified, IssuerVerified,
+    SchemaDeleted, SchemaNew, SchemaUpdated,
+};
 use crate::state::MAX_LEN_URI;
-use crate::state::{Badge, Issuer, Profile, Schema};
-use std::str::FromStr;
+use crate::state::{Badge, Issuer, Profile, ProtocolConfig, Schema, SchemaField};
 
 use anchor_lang::prelude::*;
+use gpl_session::{session_auth_or, Session, SessionError, SessionToken};
 
 // Create a badge
 
@@ -23,7 +28,7 @@ pub struct CreateBadge<'info> {
         seeds = [Issuer::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
         bump,
         has_one = authority,
-        constraint = issuer.verified @ GumError::UnverifiedIssuer
+        constraint = issuer.can_issue() @ GumError::UnverifiedIssuer
     )]
     pub issuer: Account<'info, Issuer>,
 
@@ -39,6 +44,7 @@ pub struct CreateBadge<'info> {
     #[account(
         seeds = [Schema::SEED_PREFIX.as_bytes(), schema.random_hash.as_ref()],
         bump,
+        constraint = schema.can_be_used_by(&issuer.key()) @ GumError::SchemaNotOwnedByIssuer
     )]
     pub schema: Account<'info, Schema>,
 
@@ -51,8 +57,20 @@ pub struct CreateBadge<'info> {
 }
 
 // Handler to create a badge
-pub fn create_badge_handler(ctx: Context<CreateBadge>, metadata_uri: String) -> Result<()> {
+pub fn create_badge_handler(
+    ctx: Context<CreateBadge>,
+    metadata_uri: String,
+    expires_at: Option<i64>,
+    attributes: Vec<u8>,
+) -> Result<()> {
     require!(metadata_uri.len() <= MAX_LEN_URI, GumError::URITooLong);
+    ctx.accounts.schema.validate_attributes(&attributes)?;
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            GumError::InvalidBadgeExpiry
+        );
+    }
 
     let badge = &mut ctx.accounts.badge;
 
@@ -68,6 +86,174 @@ pub fn create_badge_handler(ctx: Context<CreateBadge>, metadata_uri: String) ->
             .clone()
             .map(|account| account.key())
             .unwrap_or(ctx.accounts.authority.key()),
+        expires_at,
+        revoked: false,
+        attributes,
+        pending: ctx.accounts.schema.requires_acceptance,
+    });
+
+    emit!(BadgeNew {
+        badge: badge.key(),
+        issuer: badge.issuer,
+        holder: badge.holder,
+        schema: badge.schema,
+        update_authority: badge.update_authority,
+        metadata_uri: badge.metadata_uri.clone(),
+        expires_at,
+        attributes: badge.attributes.clone(),
+        pending: badge.pending,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Accept a pending badge
+#[derive(Accounts, Session)]
+pub struct AcceptBadge<'info> {
+    #[account(
+        mut,
+        seeds = [Badge::SEED_PREFIX.as_bytes(), badge.issuer.as_ref(), badge.schema.as_ref(), holder.key().as_ref()],
+        bump,
+        has_one = holder,
+    )]
+    pub badge: Account<'info, Badge>,
+
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            holder.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub holder: Account<'info, Profile>,
+
+    #[session(
+        signer = authority,
+        authority = holder.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+}
+
+// Handler to accept a badge
+#[session_auth_or(
+    ctx.accounts.holder.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn accept_badge_handler(ctx: Context<AcceptBadge>) -> Result<()> {
+    let badge = &mut ctx.accounts.badge;
+    require!(badge.pending, GumError::BadgeNotPending);
+
+    badge.pending = false;
+
+    emit!(BadgeAccepted {
+        badge: badge.key(),
+        issuer: badge.issuer,
+        holder: badge.holder,
+        schema: badge.schema,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Transfer a badge to another profile
+// The badge PDA is seeded by the holder, so the old badge is closed and a new one is initialized
+#[derive(Accounts, Session)]
+pub struct TransferBadge<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [Badge::SEED_PREFIX.as_bytes(), issuer.key().as_ref(), schema.key().as_ref(), from_holder.key().as_ref()],
+        bump,
+        has_one = issuer,
+        has_one = schema,
+        constraint = badge.holder == from_holder.key(),
+        close = payer
+    )]
+    pub badge: Account<'info, Badge>,
+    #[account(
+        init,
+        seeds = [Badge::SEED_PREFIX.as_bytes(), issuer.key().as_ref(), schema.key().as_ref(), to_holder.key().as_ref()],
+        bump,
+        payer = payer,
+        space = Badge::LEN
+    )]
+    pub new_badge: Account<'info, Badge>,
+
+    #[account(
+        seeds = [Issuer::SEED_PREFIX.as_bytes(), issuer.authority.key().as_ref()],
+        bump,
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        seeds = [Schema::SEED_PREFIX.as_bytes(), schema.random_hash.as_ref()],
+        bump,
+        constraint = schema.transferable @ GumError::BadgeNotTransferable
+    )]
+    pub schema: Account<'info, Schema>,
+
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_holder.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_holder: Account<'info, Profile>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            to_holder.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub to_holder: Account<'info, Profile>,
+
+    #[session(
+        signer = authority,
+        authority = from_holder.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to transfer a badge
+#[session_auth_or(
+    ctx.accounts.from_holder.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn transfer_badge_handler(ctx: Context<TransferBadge>) -> Result<()> {
+    let badge = &ctx.accounts.badge;
+    require!(!badge.revoked, GumError::BadgeRevoked);
+
+    // Moving a badge between profiles of the same authority does not need a new acceptance
+    let pending = badge.pending
+        || (ctx.accounts.schema.requires_acceptance
+            && ctx.accounts.to_holder.authority != ctx.accounts.from_holder.authority);
+
+    let new_badge = &mut ctx.accounts.new_badge;
+
+    new_badge.set_inner(Badge {
+        holder: ctx.accounts.to_holder.key(),
+        pending,
+        ..badge.clone().into_inner()
+    });
+
+    emit!(BadgeTransferred {
+        badge: new_badge.key(),
+        old_badge: badge.key(),
+        issuer: new_badge.issuer,
+        schema: new_badge.schema,
+        from_holder: ctx.accounts.from_holder.key(),
+        to_holder: ctx.accounts.to_holder.key(),
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -88,7 +274,7 @@ pub struct UpdateBadge<'info> {
     #[account(
         seeds = [Issuer::SEED_PREFIX.as_bytes(), issuer.authority.key().as_ref()],
         bump,
-        constraint = issuer.verified @ GumError::UnverifiedIssuer
+        constraint = issuer.can_issue() @ GumError::UnverifiedIssuer
     )]
     pub issuer: Account<'info, Issuer>,
 
@@ -106,12 +292,29 @@ pub struct UpdateBadge<'info> {
 }
 
 // Handler to update a badge
-pub fn update_badge_handler(ctx: Context<UpdateBadge>, metadata_uri: String) -> Result<()> {
+pub fn update_badge_handler(
+    ctx: Context<UpdateBadge>,
+    metadata_uri: String,
+    attributes: Vec<u8>,
+) -> Result<()> {
     require!(metadata_uri.len() <= MAX_LEN_URI, GumError::URITooLong);
+    ctx.accounts.schema.validate_attributes(&attributes)?;
 
     let badge = &mut ctx.accounts.badge;
+    require!(!badge.revoked, GumError::BadgeRevoked);
 
     badge.metadata_uri = metadata_uri;
+    badge.attributes = attributes;
+
+    emit!(BadgeUpdated {
+        badge: badge.key(),
+        issuer: badge.issuer,
+        holder: badge.holder,
+        schema: badge.schema,
+        metadata_uri: badge.metadata_uri.clone(),
+        attributes: badge.attributes.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
@@ -143,7 +346,8 @@ pub struct BurnBadge<'info> {
     #[account(
         seeds = [Issuer::SEED_PREFIX.as_bytes(), issuer.authority.key().as_ref()],
         bump,
-        constraint = issuer.verified @ GumError::UnverifiedIssuer
+        // The holder can always burn a badge, eg. spam from an issuer that has since been suspended
+        constraint = issuer.can_issue() || signer.key() == holder.authority.key() @ GumError::UnverifiedIssuer
     )]
     pub issuer: Account<'info, Issuer>,
 
@@ -161,13 +365,90 @@ pub struct BurnBadge<'info> {
 }
 
 // Handler to burn a badge
-pub fn burn_badge_handler(_: Context<BurnBadge>) -> Result<()> {
+pub fn burn_badge_handler(ctx: Context<BurnBadge>) -> Result<()> {
+    emit!(BadgeBurned {
+        badge: ctx.accounts.badge.key(),
+        issuer: ctx.accounts.issuer.key(),
+        holder: ctx.accounts.holder.key(),
+        schema: ctx.accounts.schema.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Revoke a badge
+// Unlike burning, the badge account is kept so that the history remains auditable
+#[derive(Accounts)]
+pub struct RevokeBadge<'info> {
+    #[account(
+        mut,
+        seeds = [Badge::SEED_PREFIX.as_bytes(), issuer.key().as_ref(), badge.schema.key().as_ref(), badge.holder.key().as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub badge: Account<'info, Badge>,
+    #[account(
+        seeds = [Issuer::SEED_PREFIX.as_bytes(), issuer.authority.key().as_ref()],
+        bump,
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        // The badge can be revoked by the issuer or update authority set in the badge
+        constraint = badge.update_authority == signer.key() || issuer.authority == signer.key() @ProgramError::MissingRequiredSignature
+    )]
+    pub signer: Signer<'info>,
+}
+
+// Handler to revoke a badge
+pub fn revoke_badge_handler(ctx: Context<RevokeBadge>) -> Result<()> {
+    let badge = &mut ctx.accounts.badge;
+    require!(!badge.revoked, GumError::BadgeRevoked);
+
+    badge.revoked = true;
+
+    emit!(BadgeRevoked {
+        badge: badge.key(),
+        issuer: badge.issuer,
+        holder: badge.holder,
+        schema: badge.schema,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
+// Assert that a profile holds an active badge from a verified issuer under a schema
+// Meant to be called through CPI by programs that gate their instructions on badges
+#[derive(Accounts)]
+pub struct AssertBadge<'info> {
+    // Badges only ever live at their PDA, matching the keys is enough
+    #[account(
+        has_one = issuer @ GumError::InvalidBadge,
+        has_one = schema @ GumError::InvalidBadge,
+        has_one = holder @ GumError::InvalidBadge,
+    )]
+    pub badge: Account<'info, Badge>,
+    pub issuer: Account<'info, Issuer>,
+    pub schema: Account<'info, Schema>,
+    pub holder: Account<'info, Profile>,
+}
+
+// Handler to assert a badge
+pub fn assert_badge_handler(ctx: Context<AssertBadge>) -> Result<()> {
+    let issuer = &ctx.accounts.issuer;
+    require!(!issuer.suspended, GumError::SuspendedIssuer);
+    require!(issuer.verified, GumError::UnverifiedIssuer);
+
+    ctx.accounts
+        .badge
+        .assert_active(Clock::get()?.unix_timestamp)
+}
+
 // Create a schema
+// A verified issuer creates schemas it owns, the protocol admins create official schemas
 #[derive(Accounts)]
-#[instruction(metadata_uri: String, random_hash: [u8; 32])]
+#[instruction(metadata_uri: String, random_hash: [u8; 32], fields: Vec<SchemaField>, requires_acceptance: bool, transferable: bool)]
 pub struct CreateSchema<'info> {
     #[account(
         init,
@@ -178,6 +459,21 @@ pub struct CreateSchema<'info> {
     )]
     pub schema: Account<'info, Schema>,
 
+    // The issuer that will own the schema, if None the schema is official
+    #[account(
+        seeds = [Issuer::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+        has_one = authority,
+        constraint = issuer.can_issue() @ GumError::UnverifiedIssuer
+    )]
+    pub issuer: Option<Account<'info, Issuer>>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -188,22 +484,45 @@ pub fn create_schema_handler(
     ctx: Context<CreateSchema>,
     metadata_uri: String,
     random_hash: [u8; 32],
+    fields: Vec<SchemaField>,
+    requires_acceptance: bool,
+    transferable: bool,
 ) -> Result<()> {
-    require_eq!(
-        ctx.accounts.authority.key(),
-        // FIXME: Move this to constant byte and importantly use a different key before deploying.
-        // This is a quick hack to test the patch.
-        Pubkey::from_str("9fyuDiDxZJ6Nfey7EhZchYkF11M6gUhfXvUyB1oFWZmX").unwrap(),
-        GumError::UnauthorizedSigner
-    );
     require!(metadata_uri.len() <= MAX_LEN_URI, GumError::URITooLong);
+    Schema::validate_fields(&fields)?;
+
+    let (authority, official) = match &ctx.accounts.issuer {
+        Some(issuer) => (issuer.key(), false),
+        None => {
+            ctx.accounts
+                .protocol_config
+                .assert_admin_signers(ctx.remaining_accounts)?;
+            (ctx.accounts.protocol_config.key(), true)
+        }
+    };
 
     let schema = &mut ctx.accounts.schema;
 
     schema.set_inner(Schema {
-        authority: ctx.accounts.authority.key(),
+        authority,
         metadata_uri,
         random_hash,
+        official,
+        fields,
+        requires_acceptance,
+        transferable,
+    });
+
+    emit!(SchemaNew {
+        schema: schema.key(),
+        authority: schema.authority,
+        metadata_uri: schema.metadata_uri.clone(),
+        random_hash,
+        official,
+        fields: schema.fields.clone(),
+        requires_acceptance,
+        transferable,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -217,28 +536,46 @@ pub struct UpdateSchema<'info> {
         mut,
         seeds = [Schema::SEED_PREFIX.as_bytes(), schema.random_hash.as_ref()],
         bump,
-        has_one = authority
     )]
     pub schema: Account<'info, Schema>,
 
+    // The issuer that owns the schema, None for official schemas
+    #[account(
+        seeds = [Issuer::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+        has_one = authority,
+    )]
+    pub issuer: Option<Account<'info, Issuer>>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     pub authority: Signer<'info>,
 }
 
 // Handler to update a schema
 pub fn update_schema_handler(ctx: Context<UpdateSchema>, metadata_uri: String) -> Result<()> {
-    require_eq!(
-        ctx.accounts.authority.key(),
-        // FIXME: Move this to constant byte and importantly use a different key before deploying.
-        // This is a quick hack to test the patch.
-        Pubkey::from_str("9fyuDiDxZJ6Nfey7EhZchYkF11M6gUhfXvUyB1oFWZmX").unwrap(),
-        GumError::UnauthorizedSigner
-    );
+    ctx.accounts.schema.assert_authority(
+        &ctx.accounts.issuer,
+        &ctx.accounts.protocol_config,
+        ctx.remaining_accounts,
+    )?;
     require!(metadata_uri.len() <= MAX_LEN_URI, GumError::URITooLong);
 
     let schema = &mut ctx.accounts.schema;
 
     schema.metadata_uri = metadata_uri;
 
+    emit!(SchemaUpdated {
+        schema: schema.key(),
+        authority: schema.authority,
+        metadata_uri: schema.metadata_uri.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
@@ -249,23 +586,82 @@ pub struct DeleteSchema<'info> {
         mut,
         seeds = [Schema::SEED_PREFIX.as_bytes(), schema.random_hash.as_ref()],
         bump,
-        has_one = authority,
         close = authority
     )]
     pub schema: Account<'info, Schema>,
 
+    // The issuer that owns the schema, None for official schemas
+    #[account(
+        seeds = [Issuer::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+        has_one = authority,
+    )]
+    pub issuer: Option<Account<'info, Issuer>>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
     pub authority: Signer<'info>,
 }
 
 // Handler to delete a schema
 pub fn delete_schema_handler(ctx: Context<DeleteSchema>) -> Result<()> {
-    require_eq!(
-        ctx.accounts.authority.key(),
-        // FIXME: Move this to constant byte and importantly use a different key before deploying.
-        // This is a quick hack to test the patch.
-        Pubkey::from_str("9fyuDiDxZJ6Nfey7EhZchYkF11M6gUhfXvUyB1oFWZmX").unwrap(),
-        GumError::UnauthorizedSigner
-    );
+    ctx.accounts.schema.assert_authority(
+        &ctx.accounts.issuer,
+        &ctx.accounts.protocol_config,
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(SchemaDeleted {
+        schema: ctx.accounts.schema.key(),
+        authority: ctx.accounts.schema.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Migrate a schema created by the legacy admin key into an official schema
+// The legacy layout ends before the official flag, so it already reads as a schema without
+// fields and fits in its account
+#[derive(Accounts)]
+pub struct MigrateSchema<'info> {
+    #[account(
+        mut,
+        seeds = [Schema::SEED_PREFIX.as_bytes(), schema.random_hash.as_ref()],
+        bump,
+        constraint = schema.authority == Schema::legacy_authority() @ GumError::InvalidLegacySchema,
+    )]
+    pub schema: Account<'info, Schema>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+// Handler to migrate a schema, signed by the threshold of admins
+pub fn migrate_schema_handler(ctx: Context<MigrateSchema>) -> Result<()> {
+    ctx.accounts
+        .protocol_config
+        .assert_admin_signers(ctx.remaining_accounts)?;
+
+    let schema = &mut ctx.accounts.schema;
+
+    schema.authority = ctx.accounts.protocol_config.key();
+    schema.official = true;
+
+    emit!(SchemaUpdated {
+        schema: schema.key(),
+        authority: schema.authority,
+        metadata_uri: schema.metadata_uri.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
@@ -294,6 +690,13 @@ pub fn create_issuer_handler(ctx: Context<CreateIssuer>) -> Result<()> {
     issuer.set_inner(Issuer {
         authority: ctx.accounts.authority.key(),
         verified: false,
+        suspended: false,
+    });
+
+    emit!(IssuerNew {
+        issuer: issuer.key(),
+        authority: issuer.authority,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -307,13 +710,19 @@ pub struct DeleteIssuer<'info> {
         seeds = [Issuer::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
         bump,
         has_one = authority,
+        constraint = !issuer.suspended @ GumError::SuspendedIssuer,
         close = authority
     )]
     pub issuer: Account<'info, Issuer>,
     pub authority: Signer<'info>,
 }
 // Handler to delete an issuer
-pub fn delete_issuer_handler(_: Context<DeleteIssuer>) -> Result<()> {
+pub fn delete_issuer_handler(ctx: Context<DeleteIssuer>) -> Result<()> {
+    emit!(IssuerDeleted {
+        issuer: ctx.accounts.issuer.key(),
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     Ok(())
 }
 
@@ -323,22 +732,128 @@ pub struct VerifyIssuer<'info> {
     #[account(mut)]
     pub issuer: Account<'info, Issuer>,
 
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+        constraint = protocol_config.is_verifier(signer.key) @ GumError::InvalidSignerToVerify
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     pub signer: Signer<'info>,
 }
 
 // Handler to verify an issuer
 pub fn verify_issuer_handler(ctx: Context<VerifyIssuer>) -> Result<()> {
-    require_eq!(
-        ctx.accounts.signer.key(),
-        // FIXME: Move this to constant byte and importantly use a different key before deploying.
-        // This is a quick hack to test the patch.
-        Pubkey::from_str("9fyuDiDxZJ6Nfey7EhZchYkF11M6gUhfXvUyB1oFWZmX").unwrap(),
-        GumError::InvalidSignerToVerify
-    );
-
     let issuer = &mut ctx.accounts.issuer;
+    require!(!issuer.suspended, GumError::SuspendedIssuer);
 
     issuer.verified = true;
 
+    emit!(IssuerVerified {
+        issuer: issuer.key(),
+        verifier: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Unverify an issuer
+// The issuer can not create badges until it is verified again
+#[derive(Accounts)]
+pub struct UnverifyIssuer<'info> {
+    #[account(mut)]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+        constraint = protocol_config.is_verifier(signer.key) @ GumError::InvalidSignerToVerify
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub signer: Signer<'info>,
+}
+
+// Handler to unverify an issuer
+pub fn unverify_issuer_handler(ctx: Context<UnverifyIssuer>) -> Result<()> {
+    let issuer = &mut ctx.accounts.issuer;
+
+    issuer.verified = false;
+
+    emit!(IssuerUnverified {
+        issuer: issuer.key(),
+        verifier: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Suspend an issuer, eg. when its authority is compromised
+// A suspended issuer can not be verified again until it is unsuspended, the existing badges are kept
+#[derive(Accounts)]
+pub struct SuspendIssuer<'info> {
+    #[account(mut)]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+        constraint = protocol_config.is_verifier(signer.key) @ GumError::InvalidSignerToVerify
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub signer: Signer<'info>,
+}
+
+// Handler to suspend an issuer
+pub fn suspend_issuer_handler(ctx: Context<SuspendIssuer>) -> Result<()> {
+    let issuer = &mut ctx.accounts.issuer;
+
+    issuer.verified = false;
+    issuer.suspended = true;
+
+    emit!(IssuerSuspended {
+        issuer: issuer.key(),
+        verifier: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Lift the suspension of an issuer
+// The issuer stays unverified, it has to be verified again to issue badges
+#[derive(Accounts)]
+pub struct UnsuspendIssuer<'info> {
+    #[account(
+        mut,
+        constraint = issuer.suspended @ GumError::IssuerNotSuspended
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+        constraint = protocol_config.is_verifier(signer.key) @ GumError::InvalidSignerToVerify
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub signer: Signer<'info>,
+}
+
+// Handler to unsuspend an issuer
+pub fn unsuspend_issuer_handler(ctx: Context<UnsuspendIssuer>) -> Result<()> {
+    let issuer = &mut ctx.accounts.issuer;
+
+    issuer.suspended = false;
+
+    emit!(IssuerUnsuspended {
+        issuer: issuer.key(),
+        verifier: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,133 @@
+use crate::errors::GumError;
+use crate::state::{Block, Profile};
+use anchor_lang::prelude::*;
+use gpl_session::{session_auth_or, Session, SessionError, SessionToken};
+use std::convert::AsRef;
+
+use crate::constants::*;
+use crate::events::{BlockDeleted, BlockNew};
+
+// Block a profile, ie blocker -> blocked
+#[derive(Accounts, Session)]
+pub struct CreateBlock<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The account that will be initialized as a Block
+    #[account(
+        init,
+        seeds = [
+            BLOCK_PREFIX_SEED.as_bytes(),
+            blocker.key().as_ref(),
+            blocked.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = Block::LEN
+    )]
+    pub block: Account<'info, Block>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            blocker.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub blocker: Account<'info, Profile>,
+
+    pub blocked: Account<'info, Profile>,
+    #[session(
+        signer = authority,
+        authority = blocker.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to create a new Block account
+#[session_auth_or(
+    ctx.accounts.blocker.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn create_block_handler(ctx: Context<CreateBlock>) -> Result<()> {
+    // CHECK that the blocker and blocked are not the same
+    require_neq!(
+        ctx.accounts.blocker.key(),
+        ctx.accounts.blocked.key(),
+        GumError::CannotBlockSelf
+    );
+
+    let block = &mut ctx.accounts.block;
+    block.blocker = *ctx.accounts.blocker.to_account_info().key;
+    block.blocked = *ctx.accounts.blocked.to_account_info().key;
+    // emit a new block event
+    emit!(BlockNew {
+        block: *block.to_account_info().key,
+        blocker: *ctx.accounts.blocker.to_account_info().key,
+        blocked: *ctx.accounts.blocked.to_account_info().key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Unblock a profile, ie blocker -> blocked
+#[derive(Accounts, Session)]
+pub struct DeleteBlock<'info> {
+    // The Block account to delete
+    #[account(
+        mut,
+        seeds = [
+            BLOCK_PREFIX_SEED.as_bytes(),
+            blocker.key().as_ref(),
+            blocked.key().as_ref(),
+        ],
+        bump,
+        has_one = blocker,
+        has_one = blocked,
+        close = refund_receiver,
+    )]
+    pub block: Account<'info, Block>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            blocker.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub blocker: Account<'info, Profile>,
+    pub blocked: Account<'info, Profile>,
+
+    #[session(
+        signer = authority,
+        authority = blocker.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = refund_receiver.key() == blocker.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to delete a Block account
+#[session_auth_or(
+    ctx.accounts.blocker.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn delete_block_handler(ctx: Context<DeleteBlock>) -> Result<()> {
+    // emit a delete block event
+    emit!(BlockDeleted {
+        block: *ctx.accounts.block.to_account_info().key,
+        blocker: *ctx.accounts.blocker.to_account_info().key,
+        blocked: *ctx.accounts.blocked.to_account_info().key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
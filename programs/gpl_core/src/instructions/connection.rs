@@ -1,15 +1,20 @@
 use crate::errors::GumError;
-use crate::state::{Connection, Profile};
+use crate::state::{Block, Connection, Profile};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    self, allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+use anchor_lang::Discriminator;
 use gpl_session::{session_auth_or, Session, SessionError, SessionToken};
 use std::convert::AsRef;
 
 use crate::constants::*;
 use crate::errors::ConnectionError;
-use crate::events::{ConnectionDeleted, ConnectionNew};
+use crate::events::{ConnectionDeleted, ConnectionNew, ConnectionRejected, ConnectionRequested};
 
 // Create a connection between two profiles, ie from_profile -> to_profile
 #[derive(Accounts, Session)]
+#[instruction(kind: u8)]
 pub struct CreateConnection<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -19,7 +24,8 @@ pub struct CreateConnection<'info> {
         seeds = [
             CONNECTION_PREFIX_SEED.as_bytes(),
             from_profile.key().as_ref(),
-            to_profile.key().as_ref()
+            to_profile.key().as_ref(),
+            Connection::kind_seed(&kind),
         ],
         bump,
         payer = payer,
@@ -36,6 +42,16 @@ pub struct CreateConnection<'info> {
     pub from_profile: Account<'info, Profile>,
 
     pub to_profile: Account<'info, Profile>,
+    /// CHECK that to_profile has not blocked from_profile, the account must not exist
+    #[account(
+        seeds = [
+            BLOCK_PREFIX_SEED.as_bytes(),
+            to_profile.key().as_ref(),
+            from_profile.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub block: UncheckedAccount<'info>,
     #[session(
         signer = authority,
         authority = from_profile.authority.key()
@@ -52,28 +68,240 @@ pub struct CreateConnection<'info> {
     ctx.accounts.from_profile.authority.key() == ctx.accounts.authority.key(),
     GumError::UnauthorizedSigner
 )]
-pub fn create_connection_handler(ctx: Context<CreateConnection>) -> Result<()> {
+pub fn create_connection_handler(ctx: Context<CreateConnection>, kind: u8) -> Result<()> {
     // CHECK that the from_profile and to_profile are not the same
     require_neq!(
         ctx.accounts.from_profile.key(),
         ctx.accounts.to_profile.key(),
         ConnectionError::CannotConnectToSelf
     );
+    Connection::validate_kind(kind)?;
+    Block::assert_not_blocked(&ctx.accounts.block)?;
+    // CHECK that the to_profile does not have to approve the connection
+    require!(
+        !Connection::requires_approval(kind, &ctx.accounts.to_profile),
+        ConnectionError::ProfileIsPrivate
+    );
 
     let connection = &mut ctx.accounts.connection;
     connection.from_profile = *ctx.accounts.from_profile.to_account_info().key;
     connection.to_profile = *ctx.accounts.to_profile.to_account_info().key;
+    connection.kind = kind;
+    connection.pending = false;
     // emit a new connection event
     emit!(ConnectionNew {
         connection: *connection.to_account_info().key,
         from_profile: *ctx.accounts.from_profile.to_account_info().key,
         to_profile: *ctx.accounts.to_profile.to_account_info().key,
+        kind,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Request a connection to a private profile, ie from_profile -> to_profile
+// The connection stays pending until the to_profile approves it
+#[derive(Accounts, Session)]
+#[instruction(kind: u8)]
+pub struct RequestConnection<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The account that will be initialized as a pending Connection
+    #[account(
+        init,
+        seeds = [
+            CONNECTION_PREFIX_SEED.as_bytes(),
+            from_profile.key().as_ref(),
+            to_profile.key().as_ref(),
+            Connection::kind_seed(&kind),
+        ],
+        bump,
+        payer = payer,
+        space = Connection::LEN
+    )]
+    pub connection: Account<'info, Connection>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_profile: Account<'info, Profile>,
+
+    pub to_profile: Account<'info, Profile>,
+    /// CHECK that to_profile has not blocked from_profile, the account must not exist
+    #[account(
+        seeds = [
+            BLOCK_PREFIX_SEED.as_bytes(),
+            to_profile.key().as_ref(),
+            from_profile.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub block: UncheckedAccount<'info>,
+    #[session(
+        signer = authority,
+        authority = from_profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to create a pending Connection account
+#[session_auth_or(
+    ctx.accounts.from_profile.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn request_connection_handler(ctx: Context<RequestConnection>, kind: u8) -> Result<()> {
+    // CHECK that the from_profile and to_profile are not the same
+    require_neq!(
+        ctx.accounts.from_profile.key(),
+        ctx.accounts.to_profile.key(),
+        ConnectionError::CannotConnectToSelf
+    );
+    Connection::validate_kind(kind)?;
+    Block::assert_not_blocked(&ctx.accounts.block)?;
+    // CHECK that the to_profile has to approve the connection
+    require!(
+        Connection::requires_approval(kind, &ctx.accounts.to_profile),
+        ConnectionError::ApprovalNotRequired
+    );
+
+    let connection = &mut ctx.accounts.connection;
+    connection.from_profile = *ctx.accounts.from_profile.to_account_info().key;
+    connection.to_profile = *ctx.accounts.to_profile.to_account_info().key;
+    connection.kind = kind;
+    connection.pending = true;
+    // emit a connection requested event
+    emit!(ConnectionRequested {
+        connection: *connection.to_account_info().key,
+        from_profile: *ctx.accounts.from_profile.to_account_info().key,
+        to_profile: *ctx.accounts.to_profile.to_account_info().key,
+        kind,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
 }
 
+// Approve a pending connection, signed by the to_profile
+#[derive(Accounts, Session)]
+pub struct ApproveConnection<'info> {
+    #[account(
+        mut,
+        seeds = [
+            CONNECTION_PREFIX_SEED.as_bytes(),
+            from_profile.key().as_ref(),
+            to_profile.key().as_ref(),
+            Connection::kind_seed(&connection.kind),
+        ],
+        bump,
+        has_one = from_profile,
+        has_one = to_profile,
+        constraint = connection.pending @ ConnectionError::ConnectionNotPending,
+    )]
+    pub connection: Account<'info, Connection>,
+    pub from_profile: Account<'info, Profile>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            to_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub to_profile: Account<'info, Profile>,
+
+    #[session(
+        signer = authority,
+        authority = to_profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+}
+
+// Handler to activate a pending Connection account
+#[session_auth_or(
+    ctx.accounts.to_profile.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn approve_connection_handler(ctx: Context<ApproveConnection>) -> Result<()> {
+    let connection = &mut ctx.accounts.connection;
+    connection.pending = false;
+    // the connection is only new to the graph once it is approved
+    emit!(ConnectionNew {
+        connection: *connection.to_account_info().key,
+        from_profile: *ctx.accounts.from_profile.to_account_info().key,
+        to_profile: *ctx.accounts.to_profile.to_account_info().key,
+        kind: connection.kind,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Reject a pending connection, signed by the to_profile
+#[derive(Accounts, Session)]
+pub struct RejectConnection<'info> {
+    #[account(
+        mut,
+        seeds = [
+            CONNECTION_PREFIX_SEED.as_bytes(),
+            from_profile.key().as_ref(),
+            to_profile.key().as_ref(),
+            Connection::kind_seed(&connection.kind),
+        ],
+        bump,
+        has_one = from_profile,
+        has_one = to_profile,
+        constraint = connection.pending @ ConnectionError::ConnectionNotPending,
+        close = refund_receiver,
+    )]
+    pub connection: Account<'info, Connection>,
+    pub from_profile: Account<'info, Profile>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            to_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub to_profile: Account<'info, Profile>,
+
+    #[session(
+        signer = authority,
+        authority = to_profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+
+    // The rent goes back to the profile that requested the connection
+    #[account(mut, constraint = refund_receiver.key() == from_profile.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+}
+
+// Handler to close a pending Connection account
+#[session_auth_or(
+    ctx.accounts.to_profile.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn reject_connection_handler(ctx: Context<RejectConnection>) -> Result<()> {
+    // emit a connection rejected event
+    emit!(ConnectionRejected {
+        connection: *ctx.accounts.connection.to_account_info().key,
+        from_profile: *ctx.accounts.from_profile.to_account_info().key,
+        to_profile: *ctx.accounts.to_profile.to_account_info().key,
+        kind: ctx.accounts.connection.kind,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
 // Delete a connection between two profiles, ie from_profile -> to_profile
 #[derive(Accounts, Session)]
 pub struct DeleteConnection<'info> {
@@ -83,7 +311,8 @@ pub struct DeleteConnection<'info> {
         seeds = [
             CONNECTION_PREFIX_SEED.as_bytes(),
             from_profile.key().as_ref(),
-            to_profile.key().as_ref()
+            to_profile.key().as_ref(),
+            Connection::kind_seed(&connection.kind),
         ],
         bump,
         has_one = from_profile,
@@ -128,7 +357,360 @@ pub fn delete_connection_handler(ctx: Context<DeleteConnection>) -> Result<()> {
         connection: *ctx.accounts.connection.to_account_info().key,
         from_profile: *ctx.accounts.from_profile.to_account_info().key,
         to_profile: *ctx.accounts.to_profile.to_account_info().key,
+        kind: ctx.accounts.connection.kind,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Delete a connection created before connections had a kind, ie from_profile -> to_profile
+// These accounts still live at the seeds without the kind and can't be read as a Connection
+#[derive(Accounts, Session)]
+pub struct DeleteLegacyConnection<'info> {
+    /// CHECK the legacy Connection account to delete, its layout is checked in the handler
+    #[account(
+        mut,
+        seeds = [
+            CONNECTION_PREFIX_SEED.as_bytes(),
+            from_profile.key().as_ref(),
+            to_profile.key().as_ref(),
+        ],
+        bump,
+        owner = crate::ID,
+    )]
+    pub connection: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_profile: Account<'info, Profile>,
+    pub to_profile: Account<'info, Profile>,
+
+    #[session(
+        signer = authority,
+        authority = from_profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = refund_receiver.key() == from_profile.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to delete a legacy Connection account
+#[session_auth_or(
+    ctx.accounts.from_profile.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn delete_legacy_connection_handler(ctx: Context<DeleteLegacyConnection>) -> Result<()> {
+    let connection = ctx.accounts.connection.to_account_info();
+    // CHECK that the account is a Connection in the layout without a kind
+    {
+        let data = connection.try_borrow_data()?;
+        require!(
+            data.len() == Connection::LEGACY_LEN && data[..8] == Connection::DISCRIMINATOR,
+            ConnectionError::InvalidLegacyConnection
+        );
+    }
+
+    // legacy connections were all follows
+    emit!(ConnectionDeleted {
+        connection: connection.key(),
+        from_profile: *ctx.accounts.from_profile.to_account_info().key,
+        to_profile: *ctx.accounts.to_profile.to_account_info().key,
+        kind: Connection::KIND_FOLLOW,
         timestamp: Clock::get()?.unix_timestamp,
     });
+
+    // close the account the same way the close constraint does
+    let refund_receiver = ctx.accounts.refund_receiver.to_account_info();
+    **refund_receiver.lamports.borrow_mut() = refund_receiver
+        .lamports()
+        .checked_add(connection.lamports())
+        .unwrap();
+    **connection.lamports.borrow_mut() = 0;
+    connection.assign(&system_program::ID);
+    connection.realloc(0, false)?;
+    Ok(())
+}
+
+// session_auth_or drops the generics of the handler, so the batch handlers which need the
+// lifetimes of the remaining accounts do the same check through this function
+fn check_session_auth<'info, T: Session<'info>>(accounts: &T, is_authority: bool) -> Result<()> {
+    if let Some(token) = accounts.session_token() {
+        require!(accounts.is_valid()?, SessionError::InvalidToken);
+        require_eq!(
+            accounts.session_authority(),
+            token.authority.key(),
+            GumError::UnauthorizedSigner
+        );
+    } else {
+        require!(is_authority, GumError::UnauthorizedSigner);
+    }
+    Ok(())
+}
+
+// Create connections from one profile to many, ie from_profile -> [to_profile]
+// The remaining accounts are triples of to_profile, the connection PDA to initialize and the
+// block PDA of to_profile -> from_profile
+#[derive(Accounts, Session)]
+pub struct CreateConnections<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_profile: Account<'info, Profile>,
+    #[session(
+        signer = authority,
+        authority = from_profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to create a batch of Connection accounts
+pub fn create_connections_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateConnections<'info>>,
+    kind: u8,
+) -> Result<()> {
+    check_session_auth(
+        ctx.accounts,
+        ctx.accounts.from_profile.authority.key() == ctx.accounts.authority.key(),
+    )?;
+    // CHECK that the remaining accounts are a bounded list of triples
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() % 3 == 0,
+        ConnectionError::InvalidConnectionBatch
+    );
+    require!(
+        ctx.remaining_accounts.len() / 3 <= Connection::MAX_BATCH_SIZE,
+        ConnectionError::ConnectionBatchTooLarge
+    );
+    Connection::validate_kind(kind)?;
+
+    let from_profile = ctx.accounts.from_profile.key();
+    let rent = Rent::get()?;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    for accounts in ctx.remaining_accounts.chunks(3) {
+        let to_profile = Account::<Profile>::try_from(&accounts[0])?;
+        let connection_info = &accounts[1];
+        let block_info = &accounts[2];
+
+        // CHECK that the from_profile and to_profile are not the same
+        require_neq!(
+            from_profile,
+            to_profile.key(),
+            ConnectionError::CannotConnectToSelf
+        );
+        // CHECK that the to_profile does not have to approve the connection
+        require!(
+            !Connection::requires_approval(kind, &to_profile),
+            ConnectionError::ProfileIsPrivate
+        );
+
+        let to_profile_key = to_profile.key();
+        let (block_key, _) = Pubkey::find_program_address(
+            &[
+                BLOCK_PREFIX_SEED.as_bytes(),
+                to_profile_key.as_ref(),
+                from_profile.as_ref(),
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            block_key,
+            block_info.key(),
+            ConnectionError::InvalidConnectionBatch
+        );
+        // an existing account at the block PDA means the profile is blocked
+        require!(block_info.data_is_empty(), GumError::BlockedByProfile);
+        let (connection_key, bump) = Pubkey::find_program_address(
+            &[
+                CONNECTION_PREFIX_SEED.as_bytes(),
+                from_profile.as_ref(),
+                to_profile_key.as_ref(),
+                Connection::kind_seed(&kind),
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            connection_key,
+            connection_info.key(),
+            ConnectionError::InvalidConnectionBatch
+        );
+
+        let signer_seeds: &[&[u8]] = &[
+            CONNECTION_PREFIX_SEED.as_bytes(),
+            from_profile.as_ref(),
+            to_profile_key.as_ref(),
+            Connection::kind_seed(&kind),
+            &[bump],
+        ];
+        // Same as the init constraint, an account funded before its creation is allocated and
+        // assigned instead, so lamports sent to the address can't block the connection
+        let current_lamports = connection_info.lamports();
+        if current_lamports == 0 {
+            create_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    CreateAccount {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: connection_info.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                rent.minimum_balance(Connection::LEN),
+                Connection::LEN as u64,
+                ctx.program_id,
+            )?;
+        } else {
+            require_keys_neq!(
+                ctx.accounts.payer.key(),
+                connection_info.key(),
+                ConnectionError::InvalidConnectionBatch
+            );
+            let required_lamports = rent
+                .minimum_balance(Connection::LEN)
+                .max(1)
+                .saturating_sub(current_lamports);
+            if required_lamports > 0 {
+                transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: connection_info.clone(),
+                        },
+                    ),
+                    required_lamports,
+                )?;
+            }
+            allocate(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Allocate {
+                        account_to_allocate: connection_info.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                Connection::LEN as u64,
+            )?;
+            assign(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Assign {
+                        account_to_assign: connection_info.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                ctx.program_id,
+            )?;
+        }
+
+        let connection = Connection {
+            from_profile,
+            to_profile: to_profile_key,
+            kind,
+            pending: false,
+        };
+        connection.try_serialize(&mut &mut connection_info.try_borrow_mut_data()?[..])?;
+
+        // emit a new connection event
+        emit!(ConnectionNew {
+            connection: connection_key,
+            from_profile,
+            to_profile: to_profile_key,
+            kind,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// Delete connections from one profile to many, ie from_profile -> [to_profile]
+// The remaining accounts are the connections to close
+#[derive(Accounts, Session)]
+pub struct DeleteConnections<'info> {
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_profile: Account<'info, Profile>,
+
+    #[session(
+        signer = authority,
+        authority = from_profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = refund_receiver.key() == from_profile.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+}
+
+// Handler to delete a batch of Connection accounts
+pub fn delete_connections_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, DeleteConnections<'info>>,
+) -> Result<()> {
+    check_session_auth(
+        ctx.accounts,
+        ctx.accounts.from_profile.authority.key() == ctx.accounts.authority.key(),
+    )?;
+    // CHECK that the remaining accounts are a bounded list
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        ConnectionError::InvalidConnectionBatch
+    );
+    require!(
+        ctx.remaining_accounts.len() <= Connection::MAX_BATCH_SIZE,
+        ConnectionError::ConnectionBatchTooLarge
+    );
+
+    let from_profile = ctx.accounts.from_profile.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    for connection_info in ctx.remaining_accounts.iter() {
+        let connection = Account::<Connection>::try_from(connection_info)?;
+        require_keys_eq!(
+            connection.from_profile,
+            from_profile,
+            ConnectionError::InvalidConnectionBatch
+        );
+
+        // emit a delete connection event
+        emit!(ConnectionDeleted {
+            connection: connection.key(),
+            from_profile,
+            to_profile: connection.to_profile,
+            kind: connection.kind,
+            timestamp,
+        });
+
+        connection.close(ctx.accounts.refund_receiver.to_account_info())?;
+    }
+
     Ok(())
 }
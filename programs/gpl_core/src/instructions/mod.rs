@@ -1,11 +1,17 @@
 mod badge;
+mod block;
 mod connection;
 mod post;
 mod profile;
+mod protocol_config;
 mod reaction;
+mod tip;
 
 pub use badge::*;
+pub use block::*;
 pub use connection::*;
 pub use post::*;
 pub use profile::*;
+pub use protocol_config::*;
 pub use reaction::*;
+pub use tip::*;
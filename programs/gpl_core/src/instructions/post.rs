@@ -1,9 +1,10 @@
 use crate::errors::{GumError, PostError};
 use crate::events::{PostCommentNew, PostDeleted, PostNew, PostUpdated};
-use crate::state::{Post, Profile, MAX_LEN_URI};
+use crate::state::{Block, Post, PostGate, Profile, MAX_LEN_URI};
 use gpl_session::{session_auth_or, Session};
 
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 use std::convert::AsRef;
 
 use crate::constants::*;
@@ -42,7 +43,7 @@ pub struct CreatePost<'info> {
         authority = profile.authority.key()
     )]
     pub session_token: Option<Account<'info, SessionToken>>,
-    
+
     pub authority: Signer<'info>,
     // The system program
     pub system_program: Program<'info, System>,
@@ -57,20 +58,31 @@ pub fn create_post_handler(
     ctx: Context<CreatePost>,
     metadata_uri: String,
     random_hash: [u8; 32],
+    mentions: Vec<Pubkey>,
+    tags: Vec<[u8; 32]>,
+    gate: Option<PostGate>,
 ) -> Result<()> {
     // CHECK metadata_uri length
     require!(metadata_uri.len() <= MAX_LEN_URI, PostError::URITooLong);
+    // CHECK mentioned profiles and tags
+    Post::validate_mentions(&mentions, ctx.remaining_accounts)?;
+    Post::validate_tags(&tags)?;
+    Post::validate_gate(&gate)?;
 
     let post = &mut ctx.accounts.post;
     post.metadata_uri = metadata_uri;
     post.random_hash = random_hash;
     post.profile = *ctx.accounts.profile.to_account_info().key;
+    post.gate = gate.clone();
     // emit new post event
     emit!(PostNew {
         post: *post.to_account_info().key,
         profile: *ctx.accounts.profile.to_account_info().key,
         random_hash: random_hash,
         metadata_uri: post.metadata_uri.clone(),
+        mentions,
+        tags,
+        gate,
         timestamp: Clock::get()?.unix_timestamp,
     });
     Ok(())
@@ -163,6 +175,16 @@ pub struct CreateComment<'info> {
         bump,
     )]
     pub reply_to: Account<'info, Post>,
+    /// CHECK that the author of the parent post has not blocked profile, the account must not exist
+    #[account(
+        seeds = [
+            BLOCK_PREFIX_SEED.as_bytes(),
+            reply_to.profile.as_ref(),
+            profile.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub block: UncheckedAccount<'info>,
     #[session(
         signer = authority,
         authority = profile.authority.key()
@@ -182,9 +204,15 @@ pub fn create_comment_handler(
     ctx: Context<CreateComment>,
     metadata_uri: String,
     random_hash: [u8; 32],
+    mentions: Vec<Pubkey>,
+    tags: Vec<[u8; 32]>,
 ) -> Result<()> {
     // Check metadata_uri length
     require!(metadata_uri.len() <= MAX_LEN_URI, PostError::URITooLong);
+    // Check mentioned profiles and tags
+    Post::validate_mentions(&mentions, ctx.remaining_accounts)?;
+    Post::validate_tags(&tags)?;
+    Block::assert_not_blocked(&ctx.accounts.block)?;
 
     let post = &mut ctx.accounts.post;
     post.metadata_uri = metadata_uri;
@@ -198,6 +226,8 @@ pub fn create_comment_handler(
         random_hash: random_hash,
         metadata_uri: post.metadata_uri.clone(),
         reply_to: *ctx.accounts.reply_to.to_account_info().key,
+        mentions,
+        tags,
         timestamp: Clock::get()?.unix_timestamp,
     });
     Ok(())
@@ -252,3 +282,72 @@ pub fn delete_post_handler(ctx: Context<DeletePost>) -> Result<()> {
     });
     Ok(())
 }
+
+// Close a comment whose parent post has been deleted
+// Anyone can clean up an orphan comment, the rent goes back to the authority of the commenting profile
+#[derive(Accounts)]
+pub struct CleanupOrphanComment<'info> {
+    #[account(
+        mut,
+        seeds = [
+            POST_PREFIX_SEED.as_bytes(),
+            post.random_hash.as_ref(),
+        ],
+        bump,
+        has_one = profile,
+        constraint = post.reply_to == Some(reply_to.key()),
+        close = refund_receiver,
+    )]
+    pub post: Account<'info, Post>,
+    /// CHECK that the post the comment replies to no longer exists
+    #[account(constraint = reply_to.data_is_empty() @ GumError::AccountNotOrphaned)]
+    pub reply_to: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut, constraint = refund_receiver.key() == profile.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+}
+
+// Handler to close an orphan comment
+pub fn cleanup_orphan_comment_handler(ctx: Context<CleanupOrphanComment>) -> Result<()> {
+    // emit delete post event
+    emit!(PostDeleted {
+        post: *ctx.accounts.post.to_account_info().key,
+        profile: *ctx.accounts.profile.to_account_info().key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Check that the holder can access a gated post
+// Downstream programs can CPI into this instead of checking the gate themselves
+#[derive(Accounts)]
+pub struct AssertAccess<'info> {
+    #[account(
+        seeds = [
+            POST_PREFIX_SEED.as_bytes(),
+            post.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub post: Account<'info, Post>,
+    #[account(constraint = token_account.owner == holder.key() @ PostError::AccessDenied)]
+    pub token_account: Account<'info, TokenAccount>,
+    /// CHECK the metadata of the token, only needed for collection gates
+    pub metadata: Option<UncheckedAccount<'info>>,
+    pub holder: Signer<'info>,
+}
+
+// Handler to check the access to a post
+pub fn assert_access_handler(ctx: Context<AssertAccess>) -> Result<()> {
+    ctx.accounts.post.assert_access(
+        &ctx.accounts.token_account,
+        ctx.accounts.metadata.as_deref(),
+    )
+}
@@ -2,7 +2,7 @@ use crate::state::Profile;
 use anchor_lang::prelude::*;
 
 use crate::constants::*;
-use crate::events::{ProfileDeleted, ProfileNew, ProfileUpdated};
+use crate::events::{ProfileDeleted, ProfileNew, ProfilePrivacyUpdated, ProfileUpdated};
 
 use gpl_nameservice::validate as validate_screen_name;
 
@@ -47,6 +47,7 @@ pub fn create_profile_handler(
         random_hash,
         metadata_uri,
         screen_name: *ctx.accounts.screen_name.key,
+        private: false,
     });
     // Emit new profile event
     emit!(ProfileNew {
@@ -96,6 +97,38 @@ pub fn update_profile_handler(ctx: Context<UpdateProfile>, metadata_uri: String)
     Ok(())
 }
 
+// Make a profile private or public
+#[derive(Accounts)]
+pub struct UpdateProfilePrivacy<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            profile.random_hash.as_ref(),
+        ],
+        bump,
+        has_one = authority,
+    )]
+    pub profile: Account<'info, Profile>,
+    pub authority: Signer<'info>,
+}
+
+// Handler to update the privacy of a Profile account
+pub fn update_profile_privacy_handler(
+    ctx: Context<UpdateProfilePrivacy>,
+    private: bool,
+) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    profile.private = private;
+    // Emit a profile privacy update event
+    emit!(ProfilePrivacyUpdated {
+        profile: *profile.to_account_info().key,
+        private,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
 // Delete a profile account
 #[derive(Accounts)]
 pub struct DeleteProfile<'info> {
@@ -0,0 +1,101 @@
+use crate::errors::ProtocolConfigError;
+use crate::events::ProtocolConfigUpdated;
+use crate::program::GplCore;
+use crate::state::ProtocolConfig;
+
+use anchor_lang::prelude::*;
+
+// Initialize the protocol config
+// Only the upgrade authority of the program can initialize it
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+        payer = authority,
+        space = ProtocolConfig::LEN
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, GplCore>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ ProtocolConfigError::UnauthorizedInitializer
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to initialize the protocol config
+pub fn initialize_protocol_config_handler(
+    ctx: Context<InitializeProtocolConfig>,
+    admins: Vec<Pubkey>,
+    threshold: u8,
+    verifiers: Vec<Pubkey>,
+) -> Result<()> {
+    ProtocolConfig::validate(&admins, threshold, &verifiers)?;
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+
+    protocol_config.set_inner(ProtocolConfig {
+        admins,
+        threshold,
+        verifiers,
+    });
+
+    emit!(ProtocolConfigUpdated {
+        protocol_config: protocol_config.key(),
+        admins: protocol_config.admins.clone(),
+        threshold,
+        verifiers: protocol_config.verifiers.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Update the protocol config
+// At least threshold admins have to sign, they are passed in the remaining accounts
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+// Handler to update the protocol config
+pub fn update_protocol_config_handler(
+    ctx: Context<UpdateProtocolConfig>,
+    admins: Vec<Pubkey>,
+    threshold: u8,
+    verifiers: Vec<Pubkey>,
+) -> Result<()> {
+    ctx.accounts
+        .protocol_config
+        .assert_admin_signers(ctx.remaining_accounts)?;
+    ProtocolConfig::validate(&admins, threshold, &verifiers)?;
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+
+    protocol_config.admins = admins;
+    protocol_config.threshold = threshold;
+    protocol_config.verifiers = verifiers;
+
+    emit!(ProtocolConfigUpdated {
+        protocol_config: protocol_config.key(),
+        admins: protocol_config.admins.clone(),
+        threshold,
+        verifiers: protocol_config.verifiers.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use crate::errors::GumError;
-use crate::state::{Post, Profile, Reaction};
+use crate::state::{BinaryReaction, Block, Post, Profile, Reaction, ReactionSet, ReactionTally};
 
 use anchor_lang::prelude::*;
 
 use crate::constants::*;
-use crate::events::{ReactionDeleted, ReactionNew};
+use crate::events::{
+    BinaryReactionDeleted, BinaryReactionNew, ReactionDeleted, ReactionNew, ReactionUpdated,
+};
 use gpl_session::{session_auth_or, Session, SessionError, SessionToken};
 
 // Create a reaction to a post from a profile
@@ -37,6 +39,32 @@ pub struct CreateReaction<'info> {
     )]
     pub from_profile: Account<'info, Profile>,
 
+    /// CHECK that the author of the post has not blocked from_profile, the account must not exist
+    #[account(
+        seeds = [
+            BLOCK_PREFIX_SEED.as_bytes(),
+            to_post.profile.as_ref(),
+            from_profile.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub block: UncheckedAccount<'info>,
+
+    /// CHECK the reaction counts of the post, only updated once the tally has been created
+    #[account(
+        mut,
+        seeds = [REACTION_TALLY_PREFIX_SEED.as_bytes(), to_post.key().as_ref()],
+        bump,
+    )]
+    pub reaction_tally: UncheckedAccount<'info>,
+
+    // The vocabulary of reaction types the app accepts, if any
+    #[account(
+        seeds = [REACTION_SET_PREFIX_SEED.as_bytes(), reaction_set.random_hash.as_ref()],
+        bump,
+    )]
+    pub reaction_set: Option<Account<'info, ReactionSet>>,
+
     #[session(
         signer = authority,
         authority = from_profile.authority.key()
@@ -56,8 +84,16 @@ pub struct CreateReaction<'info> {
 )]
 pub fn create_reaction_handler(ctx: Context<CreateReaction>, reaction_type: String) -> Result<()> {
     Reaction::validate_reaction_type(&reaction_type)?;
+    if let Some(reaction_set) = &ctx.accounts.reaction_set {
+        reaction_set.assert_allowed(&reaction_type)?;
+    }
+    Block::assert_not_blocked(&ctx.accounts.block)?;
+    let counted = ReactionTally::update(&ctx.accounts.reaction_tally, |tally| {
+        tally.increment(&reaction_type)
+    })?;
     let reaction = &mut ctx.accounts.reaction;
     reaction.reaction_type = reaction_type;
+    reaction.counted = counted;
     reaction.to_post = *ctx.accounts.to_post.to_account_info().key;
     reaction.from_profile = *ctx.accounts.from_profile.to_account_info().key;
 
@@ -73,6 +109,121 @@ pub fn create_reaction_handler(ctx: Context<CreateReaction>, reaction_type: Stri
     Ok(())
 }
 
+// Update the type of a reaction
+// The reaction type is part of the seeds, so the old account is closed and a new one is initialized
+#[derive(Accounts, Session)]
+#[instruction(new_reaction_type: String)]
+pub struct UpdateReaction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            REACTION_PREFIX_SEED.as_bytes(),
+            reaction.reaction_type.as_ref(),
+            reaction.to_post.as_ref(),
+            reaction.from_profile.as_ref(),
+        ],
+        bump,
+        has_one = to_post,
+        has_one = from_profile,
+        close = refund_receiver,
+    )]
+    pub reaction: Account<'info, Reaction>,
+    // The account that will be initialized as the updated Reaction
+    #[account(
+        init,
+        seeds = [
+            REACTION_PREFIX_SEED.as_bytes(),
+            new_reaction_type.as_bytes(),
+            to_post.to_account_info().key.as_ref(),
+            from_profile.to_account_info().key.as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = Reaction::LEN
+    )]
+    pub new_reaction: Account<'info, Reaction>,
+    pub to_post: Account<'info, Post>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_profile: Account<'info, Profile>,
+
+    /// CHECK the reaction counts of the post, only updated once the tally has been created
+    #[account(
+        mut,
+        seeds = [REACTION_TALLY_PREFIX_SEED.as_bytes(), to_post.key().as_ref()],
+        bump,
+    )]
+    pub reaction_tally: UncheckedAccount<'info>,
+
+    // The vocabulary of reaction types the app accepts, if any
+    #[account(
+        seeds = [REACTION_SET_PREFIX_SEED.as_bytes(), reaction_set.random_hash.as_ref()],
+        bump,
+    )]
+    pub reaction_set: Option<Account<'info, ReactionSet>>,
+
+    #[session(
+        signer = authority,
+        authority = from_profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = refund_receiver.key() == from_profile.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to update a Reaction account
+#[session_auth_or(
+    ctx.accounts.from_profile.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn update_reaction_handler(
+    ctx: Context<UpdateReaction>,
+    new_reaction_type: String,
+) -> Result<()> {
+    Reaction::validate_reaction_type(&new_reaction_type)?;
+    if let Some(reaction_set) = &ctx.accounts.reaction_set {
+        reaction_set.assert_allowed(&new_reaction_type)?;
+    }
+    let reaction = &ctx.accounts.reaction;
+    let counted = ReactionTally::update(&ctx.accounts.reaction_tally, |tally| {
+        if reaction.counted {
+            tally.decrement(&reaction.reaction_type);
+        }
+        tally.increment(&new_reaction_type)
+    })?;
+    let new_reaction = &mut ctx.accounts.new_reaction;
+    new_reaction.reaction_type = new_reaction_type;
+    new_reaction.counted = counted;
+    new_reaction.to_post = *ctx.accounts.to_post.to_account_info().key;
+    new_reaction.from_profile = *ctx.accounts.from_profile.to_account_info().key;
+
+    // emit a reaction updated event
+    emit!(ReactionUpdated {
+        reaction: *new_reaction.to_account_info().key,
+        old_reaction: *ctx.accounts.reaction.to_account_info().key,
+        reaction_type: new_reaction.reaction_type.clone(),
+        old_reaction_type: ctx.accounts.reaction.reaction_type.clone(),
+        to_post: *ctx.accounts.to_post.to_account_info().key,
+        from_profile: *ctx.accounts.from_profile.to_account_info().key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 // Delete a reaction account
 #[derive(Accounts, Session)]
 pub struct DeleteReaction<'info> {
@@ -100,6 +251,14 @@ pub struct DeleteReaction<'info> {
     )]
     pub from_profile: Account<'info, Profile>,
 
+    /// CHECK the reaction counts of the post, only updated once the tally has been created
+    #[account(
+        mut,
+        seeds = [REACTION_TALLY_PREFIX_SEED.as_bytes(), to_post.key().as_ref()],
+        bump,
+    )]
+    pub reaction_tally: UncheckedAccount<'info>,
+
     #[session(
         signer = authority,
         authority = from_profile.authority.key()
@@ -122,6 +281,12 @@ pub struct DeleteReaction<'info> {
     GumError::UnauthorizedSigner
 )]
 pub fn delete_reaction_handler(ctx: Context<DeleteReaction>) -> Result<()> {
+    let reaction = &ctx.accounts.reaction;
+    if reaction.counted {
+        ReactionTally::update(&ctx.accounts.reaction_tally, |tally| {
+            tally.decrement(&reaction.reaction_type)
+        })?;
+    }
     // emit a reaction deleted event
     emit!(ReactionDeleted {
         reaction: *ctx.accounts.reaction.to_account_info().key,
@@ -132,3 +297,435 @@ pub fn delete_reaction_handler(ctx: Context<DeleteReaction>) -> Result<()> {
     });
     Ok(())
 }
+
+// Close a reaction whose post has been deleted
+// Anyone can clean up an orphan reaction, the rent goes back to the authority of the reacting profile
+#[derive(Accounts)]
+pub struct CleanupOrphanReaction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            REACTION_PREFIX_SEED.as_bytes(),
+            reaction.reaction_type.as_ref(),
+            reaction.to_post.as_ref(),
+            reaction.from_profile.as_ref(),
+        ],
+        bump,
+        has_one = to_post,
+        has_one = from_profile,
+        close = refund_receiver,
+    )]
+    pub reaction: Account<'info, Reaction>,
+    /// CHECK that the post the reaction points to no longer exists
+    #[account(constraint = to_post.data_is_empty() @ GumError::AccountNotOrphaned)]
+    pub to_post: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_profile: Account<'info, Profile>,
+
+    #[account(mut, constraint = refund_receiver.key() == from_profile.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+}
+
+// Handler to close an orphan Reaction account
+pub fn cleanup_orphan_reaction_handler(ctx: Context<CleanupOrphanReaction>) -> Result<()> {
+    // emit a reaction deleted event
+    emit!(ReactionDeleted {
+        reaction: *ctx.accounts.reaction.to_account_info().key,
+        reaction_type: ctx.accounts.reaction.reaction_type.to_string(),
+        to_post: *ctx.accounts.to_post.key,
+        from_profile: *ctx.accounts.from_profile.to_account_info().key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Create a reaction with a fixed size type to a post from a profile
+#[derive(Accounts, Session)]
+#[instruction(reaction_type: [u8; 32])]
+pub struct CreateBinaryReaction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The account that will be initialized as a BinaryReaction
+    #[account(
+        init,
+        seeds = [
+            BINARY_REACTION_PREFIX_SEED.as_bytes(),
+            reaction_type.as_ref(),
+            to_post.to_account_info().key.as_ref(),
+            from_profile.to_account_info().key.as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = BinaryReaction::LEN
+    )]
+    pub reaction: Account<'info, BinaryReaction>,
+    pub to_post: Account<'info, Post>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_profile: Account<'info, Profile>,
+
+    /// CHECK that the author of the post has not blocked from_profile, the account must not exist
+    #[account(
+        seeds = [
+            BLOCK_PREFIX_SEED.as_bytes(),
+            to_post.profile.as_ref(),
+            from_profile.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub block: UncheckedAccount<'info>,
+
+    /// CHECK the reaction counts of the post, only updated once the tally has been created
+    #[account(
+        mut,
+        seeds = [REACTION_TALLY_PREFIX_SEED.as_bytes(), to_post.key().as_ref()],
+        bump,
+    )]
+    pub reaction_tally: UncheckedAccount<'info>,
+
+    #[session(
+        signer = authority,
+        authority = from_profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to create a new BinaryReaction account
+#[session_auth_or(
+    ctx.accounts.from_profile.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn create_binary_reaction_handler(
+    ctx: Context<CreateBinaryReaction>,
+    reaction_type: [u8; 32],
+) -> Result<()> {
+    Block::assert_not_blocked(&ctx.accounts.block)?;
+    let counted = ReactionTally::update(&ctx.accounts.reaction_tally, |tally| {
+        tally.increment_binary(&reaction_type)
+    })?;
+    let reaction = &mut ctx.accounts.reaction;
+    reaction.reaction_type = reaction_type;
+    reaction.counted = counted;
+    reaction.to_post = *ctx.accounts.to_post.to_account_info().key;
+    reaction.from_profile = *ctx.accounts.from_profile.to_account_info().key;
+
+    // emit a new binary reaction event
+    emit!(BinaryReactionNew {
+        reaction: *reaction.to_account_info().key,
+        reaction_type,
+        to_post: *ctx.accounts.to_post.to_account_info().key,
+        from_profile: *ctx.accounts.from_profile.to_account_info().key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Delete a binary reaction account
+#[derive(Accounts, Session)]
+pub struct DeleteBinaryReaction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            BINARY_REACTION_PREFIX_SEED.as_bytes(),
+            reaction.reaction_type.as_ref(),
+            reaction.to_post.as_ref(),
+            reaction.from_profile.as_ref(),
+        ],
+        bump,
+        has_one = to_post,
+        has_one = from_profile,
+        close = refund_receiver,
+    )]
+    pub reaction: Account<'info, BinaryReaction>,
+    pub to_post: Account<'info, Post>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_profile: Account<'info, Profile>,
+
+    /// CHECK the reaction counts of the post, only updated once the tally has been created
+    #[account(
+        mut,
+        seeds = [REACTION_TALLY_PREFIX_SEED.as_bytes(), to_post.key().as_ref()],
+        bump,
+    )]
+    pub reaction_tally: UncheckedAccount<'info>,
+
+    #[session(
+        signer = authority,
+        authority = from_profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = refund_receiver.key() == from_profile.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to delete a BinaryReaction account
+#[session_auth_or(
+    ctx.accounts.from_profile.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn delete_binary_reaction_handler(ctx: Context<DeleteBinaryReaction>) -> Result<()> {
+    let reaction = &ctx.accounts.reaction;
+    if reaction.counted {
+        ReactionTally::update(&ctx.accounts.reaction_tally, |tally| {
+            tally.decrement_binary(&reaction.reaction_type)
+        })?;
+    }
+    // emit a binary reaction deleted event
+    emit!(BinaryReactionDeleted {
+        reaction: *ctx.accounts.reaction.to_account_info().key,
+        reaction_type: ctx.accounts.reaction.reaction_type,
+        to_post: *ctx.accounts.to_post.to_account_info().key,
+        from_profile: *ctx.accounts.from_profile.to_account_info().key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Close a binary reaction whose post has been deleted
+// Anyone can clean up an orphan binary reaction, the rent goes back to the authority of the reacting profile
+#[derive(Accounts)]
+pub struct CleanupOrphanBinaryReaction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            BINARY_REACTION_PREFIX_SEED.as_bytes(),
+            reaction.reaction_type.as_ref(),
+            reaction.to_post.as_ref(),
+            reaction.from_profile.as_ref(),
+        ],
+        bump,
+        has_one = to_post,
+        has_one = from_profile,
+        close = refund_receiver,
+    )]
+    pub reaction: Account<'info, BinaryReaction>,
+    /// CHECK that the post the reaction points to no longer exists
+    #[account(constraint = to_post.data_is_empty() @ GumError::AccountNotOrphaned)]
+    pub to_post: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_profile: Account<'info, Profile>,
+
+    #[account(mut, constraint = refund_receiver.key() == from_profile.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+}
+
+// Handler to close an orphan BinaryReaction account
+pub fn cleanup_orphan_binary_reaction_handler(
+    ctx: Context<CleanupOrphanBinaryReaction>,
+) -> Result<()> {
+    // emit a binary reaction deleted event
+    emit!(BinaryReactionDeleted {
+        reaction: *ctx.accounts.reaction.to_account_info().key,
+        reaction_type: ctx.accounts.reaction.reaction_type,
+        to_post: *ctx.accounts.to_post.key,
+        from_profile: *ctx.accounts.from_profile.to_account_info().key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Create a reaction set
+#[derive(Accounts)]
+#[instruction(random_hash: [u8; 32], reaction_types: Vec<String>)]
+pub struct CreateReactionSet<'info> {
+    #[account(
+        init,
+        seeds = [REACTION_SET_PREFIX_SEED.as_bytes(), random_hash.as_ref()],
+        bump,
+        payer = authority,
+        space = ReactionSet::LEN
+    )]
+    pub reaction_set: Account<'info, ReactionSet>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to create a reaction set
+pub fn create_reaction_set_handler(
+    ctx: Context<CreateReactionSet>,
+    random_hash: [u8; 32],
+    reaction_types: Vec<String>,
+) -> Result<()> {
+    ReactionSet::validate_reaction_types(&reaction_types)?;
+
+    let reaction_set = &mut ctx.accounts.reaction_set;
+
+    reaction_set.set_inner(ReactionSet {
+        authority: ctx.accounts.authority.key(),
+        random_hash,
+        reaction_types,
+    });
+
+    Ok(())
+}
+
+// Update the reaction types of a reaction set
+#[derive(Accounts)]
+pub struct UpdateReactionSet<'info> {
+    #[account(
+        mut,
+        seeds = [REACTION_SET_PREFIX_SEED.as_bytes(), reaction_set.random_hash.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub reaction_set: Account<'info, ReactionSet>,
+
+    pub authority: Signer<'info>,
+}
+
+// Handler to update a reaction set
+pub fn update_reaction_set_handler(
+    ctx: Context<UpdateReactionSet>,
+    reaction_types: Vec<String>,
+) -> Result<()> {
+    ReactionSet::validate_reaction_types(&reaction_types)?;
+
+    let reaction_set = &mut ctx.accounts.reaction_set;
+
+    reaction_set.reaction_types = reaction_types;
+
+    Ok(())
+}
+
+// Delete a reaction set
+#[derive(Accounts)]
+pub struct DeleteReactionSet<'info> {
+    #[account(
+        mut,
+        seeds = [REACTION_SET_PREFIX_SEED.as_bytes(), reaction_set.random_hash.as_ref()],
+        bump,
+        has_one = authority,
+        close = authority
+    )]
+    pub reaction_set: Account<'info, ReactionSet>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+// Handler to delete a reaction set
+pub fn delete_reaction_set_handler(_: Context<DeleteReactionSet>) -> Result<()> {
+    Ok(())
+}
+
+// Create the account counting the reactions of a post, signed by the author of the post
+#[derive(Accounts, Session)]
+pub struct CreateReactionTally<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        seeds = [REACTION_TALLY_PREFIX_SEED.as_bytes(), post.key().as_ref()],
+        bump,
+        payer = payer,
+        space = ReactionTally::LEN
+    )]
+    pub reaction_tally: Account<'info, ReactionTally>,
+    #[account(has_one = profile)]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            profile.random_hash.as_ref(),
+        ],
+        bump,
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[session(
+        signer = authority,
+        authority = profile.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub authority: Signer<'info>,
+
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to create a reaction tally
+#[session_auth_or(
+    ctx.accounts.profile.authority.key() == ctx.accounts.authority.key(),
+    GumError::UnauthorizedSigner
+)]
+pub fn create_reaction_tally_handler(
+    ctx: Context<CreateReactionTally>,
+    reaction_types: Vec<String>,
+    binary_reaction_types: Vec<[u8; 32]>,
+) -> Result<()> {
+    let reaction_tally = ReactionTally::new(
+        ctx.accounts.post.key(),
+        ctx.accounts.profile.authority,
+        &reaction_types,
+        &binary_reaction_types,
+    )?;
+    ctx.accounts.reaction_tally.set_inner(reaction_tally);
+
+    Ok(())
+}
+
+// Close the reaction tally of a deleted post
+// Anyone can clean up an orphan tally, the rent goes back to the authority of the post author
+#[derive(Accounts)]
+pub struct CleanupOrphanReactionTally<'info> {
+    #[account(
+        mut,
+        seeds = [REACTION_TALLY_PREFIX_SEED.as_bytes(), reaction_tally.post.as_ref()],
+        bump,
+        has_one = post,
+        close = refund_receiver,
+    )]
+    pub reaction_tally: Account<'info, ReactionTally>,
+    /// CHECK that the post the tally counts no longer exists
+    #[account(constraint = post.data_is_empty() @ GumError::AccountNotOrphaned)]
+    pub post: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = refund_receiver.key() == reaction_tally.authority)]
+    pub refund_receiver: SystemAccount<'info>,
+}
+
+// Handler to close an orphan ReactionTally account
+pub fn cleanup_orphan_reaction_tally_handler(
+    _ctx: Context<CleanupOrphanReactionTally>,
+) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use crate::constants::*;
+use crate::errors::GumError;
+use crate::events::PostTipped;
+use crate::state::{Post, Profile};
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+// Tip the author of a post
+// The tip is paid in lamports, unless the token accounts are passed, in which case it is paid in
+// the SPL token of the mint
+// Session keys can not move funds, so the profile authority has to sign
+#[derive(Accounts)]
+pub struct TipPost<'info> {
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            from_profile.random_hash.as_ref(),
+        ],
+        bump,
+        has_one = authority,
+    )]
+    pub from_profile: Account<'info, Profile>,
+    #[account(
+        seeds = [
+            PROFILE_PREFIX_SEED.as_bytes(),
+            to_profile.random_hash.as_ref(),
+        ],
+        bump,
+        constraint = post.profile == to_profile.key(),
+    )]
+    pub to_profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = recipient.key() == to_profile.authority)]
+    pub recipient: SystemAccount<'info>,
+
+    pub mint: Option<Account<'info, Mint>>,
+    #[account(mut)]
+    pub sender_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+
+    // The system program
+    pub system_program: Program<'info, System>,
+}
+
+// Handler to tip a post
+pub fn tip_post_handler(ctx: Context<TipPost>, amount: u64) -> Result<()> {
+    require!(amount > 0, GumError::InvalidTipAmount);
+
+    let mint = match (
+        &ctx.accounts.mint,
+        &ctx.accounts.sender_token_account,
+        &ctx.accounts.recipient_token_account,
+        &ctx.accounts.token_program,
+    ) {
+        (None, None, None, None) => {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: ctx.accounts.recipient.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            None
+        }
+        (
+            Some(mint),
+            Some(sender_token_account),
+            Some(recipient_token_account),
+            Some(token_program),
+        ) => {
+            require_keys_eq!(
+                sender_token_account.mint,
+                mint.key(),
+                GumError::InvalidTokenAccount
+            );
+            require_keys_eq!(
+                sender_token_account.owner,
+                ctx.accounts.authority.key(),
+                GumError::InvalidTokenAccount
+            );
+            require_keys_eq!(
+                recipient_token_account.mint,
+                mint.key(),
+                GumError::InvalidTokenAccount
+            );
+            require_keys_eq!(
+                recipient_token_account.owner,
+                ctx.accounts.to_profile.authority,
+                GumError::InvalidTokenAccount
+            );
+            token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: sender_token_account.to_account_info(),
+                        to: recipient_token_account.to_account_info(),
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            Some(mint.key())
+        }
+        _ => return Err(GumError::InvalidTokenAccount.into()),
+    };
+
+    // emit a post tipped event
+    emit!(PostTipped {
+        post: ctx.accounts.post.key(),
+        from_profile: ctx.accounts.from_profile.key(),
+        to_profile: ctx.accounts.to_profile.key(),
+        mint,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
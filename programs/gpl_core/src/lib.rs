@@ -3,10 +3,13 @@ use anchor_lang::prelude::*;
 pub mod constants;
 pub mod errors;
 pub mod events;
+#[cfg(feature = "cpi")]
+pub mod helpers;
 pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::{PostGate, SchemaField};
 
 declare_id!("6MhUAJtKdJx3RDCffUsJsQm8xy9YhhywjEmMYrxRc5j6");
 #[cfg(not(feature = "no-entrypoint"))]
@@ -38,6 +41,11 @@ pub mod gpl_core {
         update_profile_handler(ctx, metadata_uri)
     }
 
+    // make a profile private or public
+    pub fn update_profile_privacy(ctx: Context<UpdateProfilePrivacy>, private: bool) -> Result<()> {
+        update_profile_privacy_handler(ctx, private)
+    }
+
     // Delete a profile account
     pub fn delete_profile(ctx: Context<DeleteProfile>) -> Result<()> {
         delete_profile_handler(ctx)
@@ -48,8 +56,11 @@ pub mod gpl_core {
         ctx: Context<CreatePost>,
         metadata_uri: String,
         random_hash: [u8; 32],
+        mentions: Vec<Pubkey>,
+        tags: Vec<[u8; 32]>,
+        gate: Option<PostGate>,
     ) -> Result<()> {
-        create_post_handler(ctx, metadata_uri, random_hash)
+        create_post_handler(ctx, metadata_uri, random_hash, mentions, tags, gate)
     }
 
     // update a post
@@ -62,8 +73,10 @@ pub mod gpl_core {
         ctx: Context<CreateComment>,
         metadata_uri: String,
         random_hash: [u8; 32],
+        mentions: Vec<Pubkey>,
+        tags: Vec<[u8; 32]>,
     ) -> Result<()> {
-        create_comment_handler(ctx, metadata_uri, random_hash)
+        create_comment_handler(ctx, metadata_uri, random_hash, mentions, tags)
     }
 
     // delete a post
@@ -71,9 +84,49 @@ pub mod gpl_core {
         delete_post_handler(ctx)
     }
 
+    // check that a holder can access a gated post
+    pub fn assert_access(ctx: Context<AssertAccess>) -> Result<()> {
+        assert_access_handler(ctx)
+    }
+
+    // close a comment whose parent post was deleted
+    pub fn cleanup_orphan_comment(ctx: Context<CleanupOrphanComment>) -> Result<()> {
+        cleanup_orphan_comment_handler(ctx)
+    }
+
     // create a connection account
-    pub fn create_connection(ctx: Context<CreateConnection>) -> Result<()> {
-        create_connection_handler(ctx)
+    pub fn create_connection(ctx: Context<CreateConnection>, kind: u8) -> Result<()> {
+        create_connection_handler(ctx, kind)
+    }
+
+    // create connections to many profiles at once
+    pub fn create_connections<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateConnections<'info>>,
+        kind: u8,
+    ) -> Result<()> {
+        create_connections_handler(ctx, kind)
+    }
+
+    // delete connections to many profiles at once
+    pub fn delete_connections<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeleteConnections<'info>>,
+    ) -> Result<()> {
+        delete_connections_handler(ctx)
+    }
+
+    // request a connection to a private profile
+    pub fn request_connection(ctx: Context<RequestConnection>, kind: u8) -> Result<()> {
+        request_connection_handler(ctx, kind)
+    }
+
+    // approve a pending connection
+    pub fn approve_connection(ctx: Context<ApproveConnection>) -> Result<()> {
+        approve_connection_handler(ctx)
+    }
+
+    // reject a pending connection
+    pub fn reject_connection(ctx: Context<RejectConnection>) -> Result<()> {
+        reject_connection_handler(ctx)
     }
 
     // delete a connection account
@@ -81,25 +134,148 @@ pub mod gpl_core {
         delete_connection_handler(ctx)
     }
 
+    // delete a connection account created before connections had a kind
+    pub fn delete_legacy_connection(ctx: Context<DeleteLegacyConnection>) -> Result<()> {
+        delete_legacy_connection_handler(ctx)
+    }
+
+    // block a profile
+    pub fn create_block(ctx: Context<CreateBlock>) -> Result<()> {
+        create_block_handler(ctx)
+    }
+
+    // unblock a profile
+    pub fn delete_block(ctx: Context<DeleteBlock>) -> Result<()> {
+        delete_block_handler(ctx)
+    }
+
+    // tip the author of a post in lamports or in an SPL token
+    pub fn tip_post(ctx: Context<TipPost>, amount: u64) -> Result<()> {
+        tip_post_handler(ctx, amount)
+    }
+
     // create a reaction account with reaction type
     pub fn create_reaction(ctx: Context<CreateReaction>, reaction_type: String) -> Result<()> {
         // By default, reactions are not custom
         create_reaction_handler(ctx, reaction_type)
     }
 
+    // update the type of a reaction
+    pub fn update_reaction(ctx: Context<UpdateReaction>, new_reaction_type: String) -> Result<()> {
+        update_reaction_handler(ctx, new_reaction_type)
+    }
+
     // delete a reaction account
     pub fn delete_reaction(ctx: Context<DeleteReaction>) -> Result<()> {
         delete_reaction_handler(ctx)
     }
 
+    // create a reaction account with a fixed size reaction type
+    pub fn create_binary_reaction(
+        ctx: Context<CreateBinaryReaction>,
+        reaction_type: [u8; 32],
+    ) -> Result<()> {
+        create_binary_reaction_handler(ctx, reaction_type)
+    }
+
+    // delete a binary reaction account
+    pub fn delete_binary_reaction(ctx: Context<DeleteBinaryReaction>) -> Result<()> {
+        delete_binary_reaction_handler(ctx)
+    }
+
+    // create a reaction set listing the reaction types an app accepts
+    pub fn create_reaction_set(
+        ctx: Context<CreateReactionSet>,
+        random_hash: [u8; 32],
+        reaction_types: Vec<String>,
+    ) -> Result<()> {
+        create_reaction_set_handler(ctx, random_hash, reaction_types)
+    }
+
+    // update the reaction types of a reaction set
+    pub fn update_reaction_set(
+        ctx: Context<UpdateReactionSet>,
+        reaction_types: Vec<String>,
+    ) -> Result<()> {
+        update_reaction_set_handler(ctx, reaction_types)
+    }
+
+    // delete a reaction set
+    pub fn delete_reaction_set(ctx: Context<DeleteReactionSet>) -> Result<()> {
+        delete_reaction_set_handler(ctx)
+    }
+
+    // create the account counting the reactions of a post
+    pub fn create_reaction_tally(
+        ctx: Context<CreateReactionTally>,
+        reaction_types: Vec<String>,
+        binary_reaction_types: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        create_reaction_tally_handler(ctx, reaction_types, binary_reaction_types)
+    }
+
+    // close the reaction tally of a deleted post
+    pub fn cleanup_orphan_reaction_tally(ctx: Context<CleanupOrphanReactionTally>) -> Result<()> {
+        cleanup_orphan_reaction_tally_handler(ctx)
+    }
+
+    // close a reaction whose post was deleted
+    pub fn cleanup_orphan_reaction(ctx: Context<CleanupOrphanReaction>) -> Result<()> {
+        cleanup_orphan_reaction_handler(ctx)
+    }
+
+    // close a binary reaction whose post was deleted
+    pub fn cleanup_orphan_binary_reaction(ctx: Context<CleanupOrphanBinaryReaction>) -> Result<()> {
+        cleanup_orphan_binary_reaction_handler(ctx)
+    }
+
+    // initialize the protocol config, only the upgrade authority can call this
+    pub fn initialize_protocol_config(
+        ctx: Context<InitializeProtocolConfig>,
+        admins: Vec<Pubkey>,
+        threshold: u8,
+        verifiers: Vec<Pubkey>,
+    ) -> Result<()> {
+        initialize_protocol_config_handler(ctx, admins, threshold, verifiers)
+    }
+
+    // update the protocol config, signed by the threshold of admins
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        admins: Vec<Pubkey>,
+        threshold: u8,
+        verifiers: Vec<Pubkey>,
+    ) -> Result<()> {
+        update_protocol_config_handler(ctx, admins, threshold, verifiers)
+    }
+
     // create a badge account
-    pub fn create_badge(ctx: Context<CreateBadge>, metadata_uri: String) -> Result<()> {
-        create_badge_handler(ctx, metadata_uri)
+    pub fn create_badge(
+        ctx: Context<CreateBadge>,
+        metadata_uri: String,
+        expires_at: Option<i64>,
+        attributes: Vec<u8>,
+    ) -> Result<()> {
+        create_badge_handler(ctx, metadata_uri, expires_at, attributes)
+    }
+
+    // accept a pending badge as its holder
+    pub fn accept_badge(ctx: Context<AcceptBadge>) -> Result<()> {
+        accept_badge_handler(ctx)
+    }
+
+    // transfer a badge to another profile
+    pub fn transfer_badge(ctx: Context<TransferBadge>) -> Result<()> {
+        transfer_badge_handler(ctx)
     }
 
     // update a badge
-    pub fn update_badge(ctx: Context<UpdateBadge>, metadata_uri: String) -> Result<()> {
-        update_badge_handler(ctx, metadata_uri)
+    pub fn update_badge(
+        ctx: Context<UpdateBadge>,
+        metadata_uri: String,
+        attributes: Vec<u8>,
+    ) -> Result<()> {
+        update_badge_handler(ctx, metadata_uri, attributes)
     }
 
     // burn a badge
@@ -107,6 +283,16 @@ pub mod gpl_core {
         burn_badge_handler(ctx)
     }
 
+    // revoke a badge, keeping the account
+    pub fn revoke_badge(ctx: Context<RevokeBadge>) -> Result<()> {
+        revoke_badge_handler(ctx)
+    }
+
+    // assert that a profile holds an active badge
+    pub fn assert_badge(ctx: Context<AssertBadge>) -> Result<()> {
+        assert_badge_handler(ctx)
+    }
+
     // create an issuer account
     pub fn create_issuer(ctx: Context<CreateIssuer>) -> Result<()> {
         create_issuer_handler(ctx)
@@ -117,6 +303,21 @@ pub mod gpl_core {
         verify_issuer_handler(ctx)
     }
 
+    // unverify an issuer
+    pub fn unverify_issuer(ctx: Context<UnverifyIssuer>) -> Result<()> {
+        unverify_issuer_handler(ctx)
+    }
+
+    // suspend an issuer
+    pub fn suspend_issuer(ctx: Context<SuspendIssuer>) -> Result<()> {
+        suspend_issuer_handler(ctx)
+    }
+
+    // lift the suspension of an issuer
+    pub fn unsuspend_issuer(ctx: Context<UnsuspendIssuer>) -> Result<()> {
+        unsuspend_issuer_handler(ctx)
+    }
+
     // delete an issuer
     pub fn delete_issuer(ctx: Context<DeleteIssuer>) -> Result<()> {
         delete_issuer_handler(ctx)
@@ -127,8 +328,18 @@ pub mod gpl_core {
         ctx: Context<CreateSchema>,
         metadata_uri: String,
         random_hash: [u8; 32],
+        fields: Vec<SchemaField>,
+        requires_acceptance: bool,
+        transferable: bool,
     ) -> Result<()> {
-        create_schema_handler(ctx, metadata_uri, random_hash)
+        create_schema_handler(
+            ctx,
+            metadata_uri,
+            random_hash,
+            fields,
+            requires_acceptance,
+            transferable,
+        )
     }
 
     // update a schema
@@ -140,4 +351,9 @@ pub mod gpl_core {
     pub fn delete_schema(ctx: Context<DeleteSchema>) -> Result<()> {
         delete_schema_handler(ctx)
     }
+
+    // migrate a schema created by the legacy admin key, signed by the threshold of admins
+    pub fn migrate_schema(ctx: Context<MigrateSchema>) -> Result<()> {
+        migrate_schema_handler(ctx)
+    }
 }
@@ -1,6 +1,8 @@
-use crate::state::MAX_LEN_URI;
+use crate::errors::GumError;
+use crate::state::{ProtocolConfig, MAX_LEN_URI};
 use anchor_lang::prelude::*;
 use std::mem::size_of;
+use std::str::FromStr;
 
 #[account]
 pub struct Badge {
@@ -10,35 +12,176 @@ pub struct Badge {
     pub update_authority: Pubkey,
     pub schema: Pubkey,
     pub metadata_uri: String,
+    // Unix timestamp after which the badge is no longer valid
+    pub expires_at: Option<i64>,
+    // Revoked badges are kept for auditability instead of being burned
+    pub revoked: bool,
+    // The values of the schema fields, borsh encoded in the order of the fields
+    pub attributes: Vec<u8>,
+    // Pending badges do not count until the holder accepts them
+    pub pending: bool,
 }
 
 impl Badge {
     pub const SEED_PREFIX: &'static str = "badge";
 
-    pub const LEN: usize = 8 + 64 + MAX_LEN_URI + size_of::<Self>();
+    pub const LEN: usize =
+        8 + 64 + MAX_LEN_URI + size_of::<Self>() + Schema::MAX_FIELDS * size_of::<Pubkey>();
+
+    // Fails with the reason the badge does not count
+    pub fn assert_active(&self, now: i64) -> Result<()> {
+        require!(!self.revoked, GumError::BadgeRevoked);
+        require!(!self.pending, GumError::BadgePending);
+        if let Some(expires_at) = self.expires_at {
+            require!(now < expires_at, GumError::BadgeExpired);
+        }
+        Ok(())
+    }
+
+    pub fn find_pda(issuer: &Pubkey, schema: &Pubkey, holder: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX.as_bytes(),
+                issuer.as_ref(),
+                schema.as_ref(),
+                holder.as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
 }
 
 #[account]
 pub struct Issuer {
     pub authority: Pubkey,
     pub verified: bool,
+    // Suspended issuers can not be verified again or deleted, their badges remain queryable
+    pub suspended: bool,
 }
 
 impl Issuer {
     pub const SEED_PREFIX: &'static str = "issuer";
 
     pub const LEN: usize = 8 + 64 + size_of::<Self>();
+
+    pub fn can_issue(&self) -> bool {
+        self.verified && !self.suspended
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    U64,
+    Bool,
+    Pubkey,
+    Bytes32,
+}
+
+impl FieldType {
+    // The borsh encoded size of a value of this type
+    pub fn size(&self) -> usize {
+        match self {
+            FieldType::U64 => 8,
+            FieldType::Bool => 1,
+            FieldType::Pubkey | FieldType::Bytes32 => 32,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct SchemaField {
+    pub name: String,
+    pub field_type: FieldType,
 }
 
 #[account]
 pub struct Schema {
+    // The issuer that owns the schema, or the protocol config for official schemas
     pub authority: Pubkey,
     pub metadata_uri: String,
     pub random_hash: [u8; 32],
+    // Official schemas are managed by the protocol admins and can be used by any issuer
+    pub official: bool,
+    // The typed attributes every badge of this schema carries
+    // The fields can not be changed once the schema is created
+    pub fields: Vec<SchemaField>,
+    // The holder has to accept the badges of this schema
+    pub requires_acceptance: bool,
+    // The holder can move the badges of this schema to another profile
+    pub transferable: bool,
 }
 
 impl Schema {
     pub const SEED_PREFIX: &'static str = "schema";
+    pub const MAX_FIELDS: usize = 8;
+    pub const MAX_FIELD_NAME_LEN: usize = 32;
+
+    pub const LEN: usize = 8
+        + 64
+        + MAX_LEN_URI
+        + size_of::<Self>()
+        + Self::MAX_FIELDS * (4 + Self::MAX_FIELD_NAME_LEN + 1);
+
+    pub fn validate_fields(fields: &[SchemaField]) -> Result<()> {
+        require!(
+            fields.len() <= Self::MAX_FIELDS,
+            GumError::InvalidSchemaFields
+        );
+        for (i, field) in fields.iter().enumerate() {
+            require!(
+                !field.name.is_empty() && field.name.len() <= Self::MAX_FIELD_NAME_LEN,
+                GumError::InvalidSchemaFields
+            );
+            require!(
+                !fields[..i].iter().any(|f| f.name == field.name),
+                GumError::InvalidSchemaFields
+            );
+        }
+        Ok(())
+    }
+
+    // The attributes have to hold exactly one value of the right type for every field
+    pub fn validate_attributes(&self, attributes: &[u8]) -> Result<()> {
+        let mut offset = 0;
+        for field in &self.fields {
+            let size = field.field_type.size();
+            let value = attributes
+                .get(offset..offset + size)
+                .ok_or(GumError::InvalidBadgeAttributes)?;
+            if field.field_type == FieldType::Bool {
+                require!(value[0] <= 1, GumError::InvalidBadgeAttributes);
+            }
+            offset += size;
+        }
+        require!(offset == attributes.len(), GumError::InvalidBadgeAttributes);
+        Ok(())
+    }
+
+    // Official schemas need the admin signatures in the remaining accounts,
+    // the others need the authority of the owning issuer
+    pub fn assert_authority(
+        &self,
+        issuer: &Option<Account<Issuer>>,
+        protocol_config: &ProtocolConfig,
+        signers: &[AccountInfo],
+    ) -> Result<()> {
+        if self.official {
+            return protocol_config.assert_admin_signers(signers);
+        }
+        match issuer {
+            Some(issuer) if issuer.key() == self.authority => Ok(()),
+            _ => Err(GumError::UnauthorizedSigner.into()),
+        }
+    }
+
+    // Schemas created before the protocol config were signed by this single admin key,
+    // they read as schemas of no issuer until they are migrated
+    pub fn legacy_authority() -> Pubkey {
+        // Unwrap because this is infallible
+        Pubkey::from_str("9fyuDiDxZJ6Nfey7EhZchYkF11M6gUhfXvUyB1oFWZmX").unwrap()
+    }
 
-    pub const LEN: usize = 8 + 64 + MAX_LEN_URI + size_of::<Self>();
+    pub fn can_be_used_by(&self, issuer: &Pubkey) -> bool {
+        self.official || self.authority == *issuer
+    }
 }
@@ -0,0 +1,21 @@
+use crate::errors::GumError;
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Block {
+    // The profile that owns this block
+    pub blocker: Pubkey,
+    // The profile that is blocked
+    pub blocked: Pubkey,
+}
+
+impl Block {
+    pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+
+    // The block PDA is required by the interactions that respect it, the seeds are checked by the
+    // accounts struct so an existing account at that address means the profile is blocked
+    pub fn assert_not_blocked(block: &UncheckedAccount) -> Result<()> {
+        require!(block.data_is_empty(), GumError::BlockedByProfile);
+        Ok(())
+    }
+}
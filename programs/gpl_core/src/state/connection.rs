@@ -1,3 +1,5 @@
+use crate::errors::ConnectionError;
+use crate::state::Profile;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -6,8 +8,50 @@ pub struct Connection {
     pub from_profile: Pubkey,
     // The profile that this connection is to
     pub to_profile: Pubkey,
+    // The kind of relationship, part of the seeds so two profiles can have one connection per kind
+    pub kind: u8,
+    // Set while a connection to a private profile waits for approval
+    pub pending: bool,
 }
 
 impl Connection {
     pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+    // Connections created before kinds existed only hold the two profiles
+    pub const LEGACY_LEN: usize = 8 + 32 + 32;
+
+    // Blocking is done through the Block account, so kind 1 is left unused
+    pub const KIND_FOLLOW: u8 = 0;
+    pub const KIND_MUTE: u8 = 2;
+    pub const KIND_CLOSE_FRIEND: u8 = 3;
+    // Kinds from here on are free for applications to define
+    pub const KIND_APPLICATION_START: u8 = 128;
+
+    // Bounded so a batch fits in the compute limits of a single transaction
+    pub const MAX_BATCH_SIZE: usize = 20;
+
+    pub fn validate_kind(kind: u8) -> Result<()> {
+        require!(
+            kind == Self::KIND_FOLLOW
+                || kind == Self::KIND_MUTE
+                || kind == Self::KIND_CLOSE_FRIEND
+                || kind >= Self::KIND_APPLICATION_START,
+            ConnectionError::InvalidConnectionKind
+        );
+        Ok(())
+    }
+
+    // Follows keep the seeds of the connections created before kinds existed, so one follow
+    // has the same address in gpl_core and in the compressed trees
+    pub fn kind_seed(kind: &u8) -> &[u8] {
+        if *kind == Self::KIND_FOLLOW {
+            &[]
+        } else {
+            std::slice::from_ref(kind)
+        }
+    }
+
+    // Muting is one sided, every other kind needs the approval of a private profile
+    pub fn requires_approval(kind: u8, to_profile: &Profile) -> bool {
+        to_profile.private && kind != Self::KIND_MUTE
+    }
 }
@@ -0,0 +1,64 @@
+use crate::errors::PostError;
+use anchor_lang::prelude::*;
+use std::str::FromStr;
+
+// The leading fields of a Metaplex token metadata account, up to the collection.
+// Only what the collection gates need is read, so the account is parsed here instead of
+// pulling in the whole token metadata crate.
+#[derive(AnchorDeserialize)]
+pub struct TokenMetadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub data: MetadataData,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<MetadataCollection>,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct MetadataData {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<MetadataCreator>>,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct MetadataCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct MetadataCollection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+impl TokenMetadata {
+    // The key Metaplex gives to metadata accounts, as opposed to editions
+    pub const KEY_METADATA_V1: u8 = 4;
+
+    pub fn program_id() -> Pubkey {
+        // Unwrap because this is infallible
+        Pubkey::from_str("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").unwrap()
+    }
+
+    // The account has to be owned by the token metadata program, and the trailing fields the
+    // gates don't read are left unparsed
+    pub fn unpack(info: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*info.owner, Self::program_id(), PostError::AccessDenied);
+        let metadata =
+            Self::deserialize(&mut &info.data.borrow()[..]).map_err(|_| PostError::AccessDenied)?;
+        require!(
+            metadata.key == Self::KEY_METADATA_V1,
+            PostError::AccessDenied
+        );
+        Ok(metadata)
+    }
+}
@@ -1,11 +1,17 @@
 mod badge;
+mod block;
 mod connection;
+mod metadata;
 mod post;
 mod profile;
+mod protocol_config;
 mod reaction;
 
 pub use badge::*;
+pub use block::*;
 pub use connection::*;
+pub use metadata::*;
 pub use post::*;
 pub use profile::*;
+pub use protocol_config::*;
 pub use reaction::*;
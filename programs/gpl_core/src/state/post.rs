@@ -1,4 +1,7 @@
+use crate::errors::PostError;
+use crate::state::{Profile, TokenMetadata};
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 
 pub const MAX_LEN_URI: usize = 128;
 
@@ -10,8 +13,104 @@ pub struct Post {
 
     //Comments are just replies
     pub reply_to: Option<Pubkey>,
+
+    // Only the holders of the token described by the gate can access the post
+    pub gate: Option<PostGate>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum GateKind {
+    // The holder needs a token of the mint
+    Mint,
+    // The holder needs a token verified as part of the collection
+    Collection,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PostGate {
+    pub kind: GateKind,
+    // The mint of the token, or the mint of the collection
+    pub key: Pubkey,
+    pub min_amount: u64,
 }
 
 impl Post {
     pub const LEN: usize = 8 + 32 + std::mem::size_of::<Self>() + MAX_LEN_URI;
+    pub const MAX_MENTIONS: usize = 8;
+    pub const MAX_TAGS: usize = 8;
+
+    // Mentions are not stored in the account, they are only emitted in the events.
+    // Every mentioned profile has to be passed in the remaining accounts, in the same order.
+    pub fn validate_mentions(mentions: &[Pubkey], profiles: &[AccountInfo]) -> Result<()> {
+        require!(
+            mentions.len() <= Self::MAX_MENTIONS,
+            PostError::TooManyMentions
+        );
+        require!(mentions.len() == profiles.len(), PostError::InvalidMention);
+        for (i, mention) in mentions.iter().enumerate() {
+            require!(
+                !mentions[..i].contains(mention),
+                PostError::DuplicateMention
+            );
+            let profile = Account::<Profile>::try_from(&profiles[i])
+                .map_err(|_| PostError::InvalidMention)?;
+            require_keys_eq!(profile.key(), *mention, PostError::InvalidMention);
+        }
+        Ok(())
+    }
+
+    // Tags are hashes of the topic, the client decides how a topic is hashed
+    pub fn validate_tags(tags: &[[u8; 32]]) -> Result<()> {
+        require!(tags.len() <= Self::MAX_TAGS, PostError::TooManyTags);
+        for (i, tag) in tags.iter().enumerate() {
+            require!(!tags[..i].contains(tag), PostError::DuplicateTag);
+        }
+        Ok(())
+    }
+
+    pub fn validate_gate(gate: &Option<PostGate>) -> Result<()> {
+        if let Some(gate) = gate {
+            require!(gate.min_amount > 0, PostError::InvalidGate);
+            // Access is checked against a single token account, and every token of a verified
+            // collection is its own mint with a supply of one
+            require!(
+                gate.kind != GateKind::Collection || gate.min_amount == 1,
+                PostError::InvalidGate
+            );
+        }
+        Ok(())
+    }
+
+    // The metadata of the token is only needed for collection gates
+    pub fn assert_access(
+        &self,
+        token_account: &TokenAccount,
+        metadata: Option<&AccountInfo>,
+    ) -> Result<()> {
+        let gate = match &self.gate {
+            Some(gate) => gate,
+            None => return Ok(()),
+        };
+        require!(
+            token_account.amount >= gate.min_amount,
+            PostError::AccessDenied
+        );
+        match gate.kind {
+            GateKind::Mint => {
+                require_keys_eq!(token_account.mint, gate.key, PostError::AccessDenied);
+            }
+            GateKind::Collection => {
+                let metadata = TokenMetadata::unpack(metadata.ok_or(PostError::AccessDenied)?)?;
+                require_keys_eq!(metadata.mint, token_account.mint, PostError::AccessDenied);
+                require!(
+                    matches!(
+                        &metadata.collection,
+                        Some(collection) if collection.verified && collection.key == gate.key
+                    ),
+                    PostError::AccessDenied
+                );
+            }
+        }
+        Ok(())
+    }
 }
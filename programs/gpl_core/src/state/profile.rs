@@ -13,6 +13,9 @@ pub struct Profile {
     pub screen_name: Pubkey,
 
     pub random_hash: [u8; 32],
+
+    // Private profiles have to approve connections before they become active
+    pub private: bool,
 }
 
 impl Profile {
@@ -0,0 +1,67 @@
+use crate::errors::ProtocolConfigError;
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+// The protocol wide configuration, there is a single ProtocolConfig account
+// It is managed by a set of admins, any update has to be signed by at least threshold of them
+#[account]
+pub struct ProtocolConfig {
+    pub admins: Vec<Pubkey>,
+    pub threshold: u8,
+    // The keys allowed to verify issuers
+    pub verifiers: Vec<Pubkey>,
+}
+
+impl ProtocolConfig {
+    pub const SEED_PREFIX: &'static str = "protocol_config";
+
+    pub const MAX_ADMINS: usize = 5;
+    pub const MAX_VERIFIERS: usize = 10;
+
+    pub const LEN: usize =
+        8 + size_of::<Self>() + (Self::MAX_ADMINS + Self::MAX_VERIFIERS) * size_of::<Pubkey>();
+
+    pub fn validate(admins: &[Pubkey], threshold: u8, verifiers: &[Pubkey]) -> Result<()> {
+        require!(
+            !admins.is_empty() && admins.len() <= Self::MAX_ADMINS,
+            ProtocolConfigError::InvalidAdmins
+        );
+        for (i, admin) in admins.iter().enumerate() {
+            require!(
+                !admins[..i].contains(admin),
+                ProtocolConfigError::InvalidAdmins
+            );
+        }
+        require!(
+            threshold > 0 && threshold as usize <= admins.len(),
+            ProtocolConfigError::InvalidThreshold
+        );
+        require!(
+            verifiers.len() <= Self::MAX_VERIFIERS,
+            ProtocolConfigError::TooManyVerifiers
+        );
+        Ok(())
+    }
+
+    // The admins sign the transaction and are passed in the remaining accounts
+    pub fn assert_admin_signers(&self, signers: &[AccountInfo]) -> Result<()> {
+        let mut approvals: Vec<Pubkey> = Vec::with_capacity(self.admins.len());
+        for signer in signers {
+            if signer.is_signer
+                && self.admins.contains(signer.key)
+                && !approvals.contains(signer.key)
+            {
+                approvals.push(*signer.key);
+            }
+        }
+        require!(
+            approvals.len() >= self.threshold as usize,
+            ProtocolConfigError::NotEnoughAdminSigners
+        );
+        Ok(())
+    }
+
+    pub fn is_verifier(&self, key: &Pubkey) -> bool {
+        self.verifiers.contains(key)
+    }
+}
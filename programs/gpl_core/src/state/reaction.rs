@@ -11,9 +11,13 @@ pub struct Reaction {
     // The burden of validating the reaction is on the client
     // Since it is hard to define what a valid reaction is and will vary from app to app
     // It's better to let the client decide what is valid
+    // An app can restrict the types it accepts by registering a ReactionSet
     //
-    // Might change this to a [u8; 32] in the future
+    // See BinaryReaction for reactions with a fixed size type
     pub reaction_type: String,
+    // Set when the reaction is part of the counts of the ReactionTally of the post
+    // Reactions created before the tally, or of a type it doesn't track, are never counted
+    pub counted: bool,
 }
 
 impl Reaction {
@@ -28,3 +32,199 @@ impl Reaction {
         Ok(())
     }
 }
+
+// A vocabulary of reaction types registered by an app
+// When a ReactionSet is passed to create_reaction, types outside of it are rejected
+#[account]
+pub struct ReactionSet {
+    pub authority: Pubkey,
+    pub random_hash: [u8; 32],
+    pub reaction_types: Vec<String>,
+}
+
+impl ReactionSet {
+    pub const MAX_REACTION_TYPES: usize = 16;
+    pub const LEN: usize = 8
+        + std::mem::size_of::<Self>()
+        + Self::MAX_REACTION_TYPES * (4 + Reaction::REACTION_TYPE_MAX_LEN);
+
+    pub fn validate_reaction_types(reaction_types: &[String]) -> Result<()> {
+        require!(
+            reaction_types.len() <= Self::MAX_REACTION_TYPES,
+            GumError::TooManyReactionTypes
+        );
+        for reaction_type in reaction_types {
+            Reaction::validate_reaction_type(reaction_type)?;
+        }
+        Ok(())
+    }
+
+    pub fn assert_allowed(&self, reaction_type: &str) -> Result<()> {
+        require!(
+            self.reaction_types.iter().any(|t| t == reaction_type),
+            GumError::ReactionTypeNotAllowed
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReactionCount {
+    pub reaction_type: String,
+    pub count: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BinaryReactionCount {
+    pub reaction_type: [u8; 32],
+    pub count: u64,
+}
+
+// The number of reactions of each tracked type on a post
+// The author of the post picks the tracked types when creating the tally, other types are left
+// to the indexer
+#[account]
+pub struct ReactionTally {
+    pub post: Pubkey,
+    // The authority of the post author, gets the rent back once the post is deleted
+    pub authority: Pubkey,
+    pub counts: Vec<ReactionCount>,
+    pub binary_counts: Vec<BinaryReactionCount>,
+}
+
+impl ReactionTally {
+    pub const MAX_TRACKED_TYPES: usize = 8;
+    pub const LEN: usize = 8
+        + std::mem::size_of::<Self>()
+        + Self::MAX_TRACKED_TYPES
+            * (4 + Reaction::REACTION_TYPE_MAX_LEN + std::mem::size_of::<u64>())
+        + Self::MAX_TRACKED_TYPES * std::mem::size_of::<BinaryReactionCount>();
+
+    pub fn new(
+        post: Pubkey,
+        authority: Pubkey,
+        reaction_types: &[String],
+        binary_reaction_types: &[[u8; 32]],
+    ) -> Result<Self> {
+        require!(
+            reaction_types.len() <= Self::MAX_TRACKED_TYPES
+                && binary_reaction_types.len() <= Self::MAX_TRACKED_TYPES,
+            GumError::TooManyReactionTypes
+        );
+        let mut tally = Self {
+            post,
+            authority,
+            counts: vec![],
+            binary_counts: vec![],
+        };
+        for reaction_type in reaction_types {
+            Reaction::validate_reaction_type(reaction_type)?;
+            if !tally
+                .counts
+                .iter()
+                .any(|c| &c.reaction_type == reaction_type)
+            {
+                tally.counts.push(ReactionCount {
+                    reaction_type: reaction_type.clone(),
+                    count: 0,
+                });
+            }
+        }
+        for reaction_type in binary_reaction_types {
+            if !tally
+                .binary_counts
+                .iter()
+                .any(|c| &c.reaction_type == reaction_type)
+            {
+                tally.binary_counts.push(BinaryReactionCount {
+                    reaction_type: *reaction_type,
+                    count: 0,
+                });
+            }
+        }
+        Ok(tally)
+    }
+
+    // The tally PDA is required by every reaction instruction of the post, so the counts can't be
+    // skipped once the tally exists. Nothing is counted while it hasn't been created.
+    pub fn update<'info, T: Default>(
+        reaction_tally: &AccountInfo<'info>,
+        update: impl FnOnce(&mut Self) -> T,
+    ) -> Result<T> {
+        if reaction_tally.owner != &crate::ID {
+            return Ok(T::default());
+        }
+        let mut tally = Account::<Self>::try_from(reaction_tally)?;
+        let result = update(&mut tally);
+        tally.exit(&crate::ID)?;
+        Ok(result)
+    }
+
+    // Returns whether the reaction was counted, ie. its type is tracked
+    pub fn increment(&mut self, reaction_type: &str) -> bool {
+        match self
+            .counts
+            .iter_mut()
+            .find(|c| c.reaction_type == reaction_type)
+        {
+            Some(count) => {
+                count.count += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Only called for reactions that were counted
+    pub fn decrement(&mut self, reaction_type: &str) {
+        if let Some(count) = self
+            .counts
+            .iter_mut()
+            .find(|c| c.reaction_type == reaction_type)
+        {
+            count.count = count.count.saturating_sub(1);
+        }
+    }
+
+    pub fn increment_binary(&mut self, reaction_type: &[u8; 32]) -> bool {
+        match self
+            .binary_counts
+            .iter_mut()
+            .find(|c| &c.reaction_type == reaction_type)
+        {
+            Some(count) => {
+                count.count += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn decrement_binary(&mut self, reaction_type: &[u8; 32]) {
+        if let Some(count) = self
+            .binary_counts
+            .iter_mut()
+            .find(|c| &c.reaction_type == reaction_type)
+        {
+            count.count = count.count.saturating_sub(1);
+        }
+    }
+}
+
+// A reaction with a fixed size type, eg. the hash of an emoji or an application defined code
+// The type is part of the seeds, so a profile can leave one reaction of each type on a post and
+// the reaction can be looked up without knowing how the type was encoded as a string
+#[account]
+pub struct BinaryReaction {
+    // The profile that owns this reaction
+    pub from_profile: Pubkey,
+    // The post that this reaction is to
+    pub to_post: Pubkey,
+    pub reaction_type: [u8; 32],
+    // Set when the reaction is part of the binary counts of the ReactionTally of the post
+    pub counted: bool,
+}
+
+impl BinaryReaction {
+    pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+}
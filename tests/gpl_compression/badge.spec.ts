@@ -0,0 +1,380 @@
+import * as anchor from "@project-serum/anchor";
+
+import {
+  airdrop,
+  gpl_core,
+  gpl_compression,
+  setupTree,
+  to_leaf,
+  assert_tree,
+  createGumTld,
+  createGumDomain,
+} from "../utils/index";
+
+import {
+  ConcurrentMerkleTreeAccount,
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+  SPL_NOOP_PROGRAM_ID,
+  MerkleTree,
+} from "@solana/spl-account-compression";
+
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+import randomBytes from "randombytes";
+
+import { expect } from "chai";
+
+import { faker } from "@faker-js/faker";
+
+anchor.setProvider(anchor.AnchorProvider.env());
+const provider = anchor.getProvider();
+const rpcConnection = provider.connection;
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+describe("Badge Compression", async () => {
+  let payer: Keypair;
+  let merkleTree: PublicKey;
+  let treeConfigPDA: PublicKey;
+  let offChainTree: MerkleTree;
+
+  let profilePDA: PublicKey;
+  let issuerPDA: PublicKey;
+  let schemaPDA: PublicKey;
+
+  beforeEach(async () => {
+    // Setup a new keypair and airdrop some SOL
+    payer = anchor.web3.Keypair.generate();
+    await airdrop(payer.publicKey);
+    const treeResult = await setupTree(
+      payer,
+      {
+        maxDepth: 14,
+        maxBufferSize: 64,
+      },
+      rpcConnection
+    );
+    merkleTree = treeResult.merkleTree;
+    treeConfigPDA = treeResult.treeConfigPDA;
+    offChainTree = treeResult.offChainTree;
+
+    const gumTld = await createGumTld();
+
+    // Set up a holder profile
+    const screenName = await createGumDomain(
+      gumTld,
+      faker.internet.userName(),
+      payer
+    );
+    const profileTx = gpl_core.methods
+      .createProfile(randomBytes(32), "https://example.com")
+      .accounts({ authority: payer.publicKey, screenName });
+    profilePDA = (await profileTx.pubkeys()).profile;
+    await profileTx.signers([payer]).rpc();
+
+    // The protocol config is a singleton, it may already be initialized
+    const [protocolConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      gpl_core.programId
+    );
+    const protocolConfig = await gpl_core.account.protocolConfig.fetchNullable(
+      protocolConfigPDA
+    );
+    if (protocolConfig === null) {
+      const [programData] = PublicKey.findProgramAddressSync(
+        [gpl_core.programId.toBuffer()],
+        BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+      );
+      await gpl_core.methods
+        .initializeProtocolConfig([provider.publicKey], 1, [provider.publicKey])
+        .accounts({ program: gpl_core.programId, programData })
+        .rpc();
+    }
+
+    // Set up a verified issuer and one of its schemas
+    const issuerTx = gpl_core.methods
+      .createIssuer()
+      .accounts({ authority: payer.publicKey });
+    issuerPDA = (await issuerTx.pubkeys()).issuer;
+    await issuerTx.signers([payer]).rpc();
+
+    await gpl_core.methods
+      .verifyIssuer()
+      .accounts({
+        issuer: issuerPDA,
+        protocolConfig: protocolConfigPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    const schemaTx = gpl_core.methods
+      .createSchema(
+        "https://example.com/schema",
+        randomBytes(32),
+        [],
+        false,
+        false
+      )
+      .accounts({
+        issuer: issuerPDA,
+        protocolConfig: protocolConfigPDA,
+        authority: payer.publicKey,
+      });
+    schemaPDA = (await schemaTx.pubkeys()).schema;
+    await schemaTx.signers([payer]).rpc();
+  });
+
+  it("should create, update and burn a compressed badge", async () => {
+    const metadataUri = "https://example.com/badge";
+    const accounts = {
+      issuer: issuerPDA,
+      holder: profilePDA,
+      schema: schemaPDA,
+      treeConfig: treeConfigPDA,
+      merkleTree,
+      authority: payer.publicKey,
+      delegate: null,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      logWrapperProgram: SPL_NOOP_PROGRAM_ID,
+      gplCoreProgram: gpl_core.programId,
+    };
+
+    await gpl_compression.methods
+      .createCompressedBadge(metadataUri, null, Buffer.from([]))
+      .accounts(accounts)
+      .signers([payer])
+      .rpc();
+
+    const badgeSeeds = [
+      Buffer.from("badge"),
+      issuerPDA.toBuffer(),
+      schemaPDA.toBuffer(),
+      profilePDA.toBuffer(),
+    ];
+    const badge = {
+      issuer: issuerPDA,
+      holder: profilePDA,
+      updateAuthority: payer.publicKey,
+      schema: schemaPDA,
+      metadataUri,
+      expiresAt: null,
+      revoked: false,
+      attributes: Buffer.from([]),
+      pending: false,
+    };
+
+    const index = 0;
+    const badgeLeaf = await to_leaf(merkleTree, "Badge", badge, badgeSeeds);
+    offChainTree.updateLeaf(index, badgeLeaf);
+
+    let treeData = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+      rpcConnection,
+      merkleTree
+    );
+    expect(assert_tree(treeData, offChainTree)).to.equal(true);
+
+    // Update the badge
+    const newMetadataUri = "https://example.com/badge/updated";
+    let proof = offChainTree.getProof(index);
+    let remainingAccounts = proof.proof.map((p) => {
+      return { pubkey: new PublicKey(p), isWritable: false, isSigner: false };
+    });
+    await gpl_compression.methods
+      .updateCompressedBadge(
+        metadataUri,
+        newMetadataUri,
+        null,
+        Buffer.from([]),
+        Buffer.from([]),
+        false,
+        // @ts-ignore
+        proof.root,
+        index
+      )
+      .accounts(accounts)
+      .remainingAccounts(remainingAccounts)
+      .signers([payer])
+      .rpc();
+
+    const newBadgeLeaf = await to_leaf(
+      merkleTree,
+      "Badge",
+      { ...badge, metadataUri: newMetadataUri },
+      badgeSeeds
+    );
+    offChainTree.updateLeaf(index, newBadgeLeaf);
+
+    treeData = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+      rpcConnection,
+      merkleTree
+    );
+    expect(assert_tree(treeData, offChainTree)).to.equal(true);
+
+    // Burn the badge
+    proof = offChainTree.getProof(index);
+    remainingAccounts = proof.proof.map((p) => {
+      return { pubkey: new PublicKey(p), isWritable: false, isSigner: false };
+    });
+    await gpl_compression.methods
+      .burnCompressedBadge(
+        newMetadataUri,
+        null,
+        Buffer.from([]),
+        false,
+        // @ts-ignore
+        proof.root,
+        index
+      )
+      .accounts(accounts)
+      .remainingAccounts(remainingAccounts)
+      .signers([payer])
+      .rpc();
+
+    offChainTree.updateLeaf(index, Buffer.alloc(32));
+
+    treeData = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+      rpcConnection,
+      merkleTree
+    );
+    expect(assert_tree(treeData, offChainTree)).to.equal(true);
+  });
+
+  it("should accept a pending compressed badge as its holder", async () => {
+    const [protocolConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      gpl_core.programId
+    );
+    const schemaTx = gpl_core.methods
+      .createSchema(
+        "https://example.com/schema/pending",
+        randomBytes(32),
+        [],
+        true,
+        false
+      )
+      .accounts({
+        issuer: issuerPDA,
+        protocolConfig: protocolConfigPDA,
+        authority: payer.publicKey,
+      });
+    const pendingSchemaPDA = (await schemaTx.pubkeys()).schema;
+    await schemaTx.signers([payer]).rpc();
+
+    const metadataUri = "https://example.com/badge";
+    // The payer is the authority of both the issuer and the holder
+    const accounts = {
+      issuer: issuerPDA,
+      holder: profilePDA,
+      schema: pendingSchemaPDA,
+      treeConfig: treeConfigPDA,
+      merkleTree,
+      authority: payer.publicKey,
+      delegate: null,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      logWrapperProgram: SPL_NOOP_PROGRAM_ID,
+      gplCoreProgram: gpl_core.programId,
+    };
+
+    await gpl_compression.methods
+      .createCompressedBadge(metadataUri, null, Buffer.from([]))
+      .accounts(accounts)
+      .signers([payer])
+      .rpc();
+
+    const badgeSeeds = [
+      Buffer.from("badge"),
+      issuerPDA.toBuffer(),
+      pendingSchemaPDA.toBuffer(),
+      profilePDA.toBuffer(),
+    ];
+    const badge = {
+      issuer: issuerPDA,
+      holder: profilePDA,
+      updateAuthority: payer.publicKey,
+      schema: pendingSchemaPDA,
+      metadataUri,
+      expiresAt: null,
+      revoked: false,
+      attributes: Buffer.from([]),
+      pending: true,
+    };
+
+    const index = 0;
+    offChainTree.updateLeaf(
+      index,
+      await to_leaf(merkleTree, "Badge", badge, badgeSeeds)
+    );
+
+    const proof = offChainTree.getProof(index);
+    const remainingAccounts = proof.proof.map((p) => {
+      return { pubkey: new PublicKey(p), isWritable: false, isSigner: false };
+    });
+    await gpl_compression.methods
+      .acceptCompressedBadge(
+        metadataUri,
+        null,
+        Buffer.from([]),
+        // @ts-ignore
+        proof.root,
+        index
+      )
+      .accounts(accounts)
+      .remainingAccounts(remainingAccounts)
+      .signers([payer])
+      .rpc();
+
+    offChainTree.updateLeaf(
+      index,
+      await to_leaf(
+        merkleTree,
+        "Badge",
+        { ...badge, pending: false },
+        badgeSeeds
+      )
+    );
+
+    const treeData = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+      rpcConnection,
+      merkleTree
+    );
+    expect(assert_tree(treeData, offChainTree)).to.equal(true);
+  });
+
+  it("should not create a compressed badge with an unverified issuer", async () => {
+    const issuer = anchor.web3.Keypair.generate();
+    await airdrop(issuer.publicKey);
+    const issuerTx = gpl_core.methods
+      .createIssuer()
+      .accounts({ authority: issuer.publicKey });
+    const unverifiedIssuerPDA = (await issuerTx.pubkeys()).issuer;
+    await issuerTx.signers([issuer]).rpc();
+
+    try {
+      await gpl_compression.methods
+        .createCompressedBadge(
+          "https://example.com/badge",
+          null,
+          Buffer.from([])
+        )
+        .accounts({
+          issuer: unverifiedIssuerPDA,
+          holder: profilePDA,
+          schema: schemaPDA,
+          treeConfig: treeConfigPDA,
+          merkleTree,
+          authority: issuer.publicKey,
+          delegate: null,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          logWrapperProgram: SPL_NOOP_PROGRAM_ID,
+          gplCoreProgram: gpl_core.programId,
+        })
+        .signers([issuer])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: UnverifiedIssuer");
+    }
+  });
+});
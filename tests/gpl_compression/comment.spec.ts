@@ -6,6 +6,8 @@ import {
   gpl_compression,
   setupTree,
   to_leaf,
+  to_comment_leaf,
+  assert_tree,
 } from "../utils/index";
 
 import {
@@ -36,6 +38,8 @@ describe("Comment Compression", async () => {
 
   let profilePDA: PublicKey;
   let postPDA: PublicKey;
+  let postRandomHash: Buffer;
+  const postMetadataUri = "https://example.com";
 
   beforeEach(async () => {
     // Setup a new keypair and airdrop some SOL
@@ -72,8 +76,7 @@ describe("Comment Compression", async () => {
     await profileTx.signers([payer]).rpc();
 
     // Create a post
-    const postRandomHash = randomBytes(32);
-    const metadataUri = "https://example.com";
+    postRandomHash = randomBytes(32);
     const postSeeds = [Buffer.from("post"), postRandomHash];
     const [post, _] = await PublicKey.findProgramAddress(
       postSeeds,
@@ -83,12 +86,13 @@ describe("Comment Compression", async () => {
     postPDA = post;
 
     await gpl_compression.methods
-      .createCompressedPost(metadataUri, postRandomHash)
+      .createCompressedPost(postMetadataUri, postRandomHash)
       .accounts({
         profile: profilePDA,
         treeConfig: treeConfigPDA,
         merkleTree,
         authority: payer.publicKey,
+        delegate: null,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
         logWrapperProgram: SPL_NOOP_PROGRAM_ID,
         gplCoreProgram: gpl_core.programId,
@@ -98,7 +102,7 @@ describe("Comment Compression", async () => {
 
     const postData = {
       profile: profilePDA,
-      metadataUri: metadataUri,
+      metadataUri: postMetadataUri,
       randomHash: postRandomHash,
       replyTo: null,
     };
@@ -107,15 +111,9 @@ describe("Comment Compression", async () => {
     offChainTree.updateLeaf(0, postLeaf);
   });
 
-  it("should create a compressed comment", async () => {
-    const randomHash = randomBytes(32);
-    const metadataUri = "https://example.com";
-    const index = 0;
-    let treeData = await ConcurrentMerkleTreeAccount.fromAccountAddress(
-      rpcConnection,
-      merkleTree
-    );
-    const proof = offChainTree.getProof(index);
+  // Comment on the compressed post at index 0, the comment is appended at index 1
+  async function createComment(randomHash: Buffer, metadataUri: string) {
+    const proof = offChainTree.getProof(0);
     const remainingAccounts = proof.proof.map((p) => {
       return { pubkey: new PublicKey(p), isWritable: false, isSigner: false };
     });
@@ -125,9 +123,12 @@ describe("Comment Compression", async () => {
         postPDA,
         metadataUri,
         randomHash,
+        profilePDA,
+        postMetadataUri,
+        postRandomHash,
+        null,
         proof.root,
-        proof.leaf,
-        index
+        0
       )
       .accounts({
         fromProfile: profilePDA,
@@ -136,6 +137,7 @@ describe("Comment Compression", async () => {
         targetTreeConfig: treeConfigPDA,
         targetMerkleTree: merkleTree,
         authority: payer.publicKey,
+        delegate: null,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
         logWrapperProgram: SPL_NOOP_PROGRAM_ID,
         gplCoreProgram: gpl_core.programId,
@@ -143,19 +145,72 @@ describe("Comment Compression", async () => {
       .remainingAccounts(remainingAccounts)
       .signers([payer])
       .rpc();
-    treeData = await ConcurrentMerkleTreeAccount.fromAccountAddress(
-      rpcConnection,
-      merkleTree
-    );
-    expect(treeData).to.not.be.null;
-    const postSeeds = [Buffer.from("post"), randomHash];
-    const post = {
+
+    const commentSeeds = [Buffer.from("post"), randomHash];
+    const comment = {
+      profile: profilePDA,
       metadataUri,
       randomHash,
-      profile: profilePDA,
       replyTo: postPDA,
     };
-    const commentLeaf = await to_leaf(merkleTree, "Post", post, postSeeds);
-    offChainTree.updateLeaf(index, commentLeaf);
+    const commentLeaf = await to_comment_leaf(
+      merkleTree,
+      comment,
+      commentSeeds
+    );
+    offChainTree.updateLeaf(1, commentLeaf);
+  }
+
+  it("should create a compressed comment", async () => {
+    await createComment(randomBytes(32), "https://example.com");
+
+    const treeData = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+      rpcConnection,
+      merkleTree
+    );
+    expect(assert_tree(treeData, offChainTree)).to.equal(true);
+  });
+
+  it("should react to a compressed comment", async () => {
+    const commentRandomHash = randomBytes(32);
+    const commentMetadataUri = "https://example.com/comment";
+    await createComment(commentRandomHash, commentMetadataUri);
+    const [commentPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("post"), commentRandomHash],
+      gpl_core.programId
+    );
+
+    // The comment leaf is rebuilt with the post it replies to
+    const proof = offChainTree.getProof(1);
+    const remainingAccounts = proof.proof.map((p) => {
+      return { pubkey: new PublicKey(p), isWritable: false, isSigner: false };
+    });
+    await gpl_compression.methods
+      .createCompressedReaction(
+        //@ts-ignore
+        commentPDA,
+        "👍",
+        profilePDA,
+        commentMetadataUri,
+        commentRandomHash,
+        postPDA,
+        proof.root,
+        1
+      )
+      .accounts({
+        fromProfile: profilePDA,
+        treeConfig: treeConfigPDA,
+        merkleTree,
+        targetTreeConfig: treeConfigPDA,
+        targetMerkleTree: merkleTree,
+        authority: payer.publicKey,
+        delegate: null,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        logWrapperProgram: SPL_NOOP_PROGRAM_ID,
+        gplCoreProgram: gpl_core.programId,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([payer])
+      .rpc();
   });
 });
@@ -1,5 +1,10 @@
 import * as anchor from "@project-serum/anchor";
-import { airdrop, setupTree, to_leaf } from "../utils/index";
+import {
+  airdrop,
+  gpl_compression,
+  setupTree,
+  to_leaf,
+} from "../utils/index";
 import { ConcurrentMerkleTreeAccount } from "@solana/spl-account-compression";
 
 import { Keypair } from "@solana/web3.js";
@@ -34,7 +39,7 @@ describe("Compression", async () => {
   });
 
   it("should set up the tree", async () => {
-    const { merkleTree } = await setupTree(
+    const { merkleTree, treeConfigPDA } = await setupTree(
       payer,
       {
         maxDepth: 14,
@@ -49,5 +54,37 @@ describe("Compression", async () => {
     );
 
     expect(merkleTreeData).to.not.be.null;
+    // Writes to the tree go through the tree config
+    expect(merkleTreeData.getAuthority().toBase58()).to.equal(
+      treeConfigPDA.toBase58()
+    );
+  });
+
+  it("should only migrate a legacy tree config", async () => {
+    const { merkleTree, treeConfigPDA } = await setupTree(
+      payer,
+      {
+        maxDepth: 14,
+        maxBufferSize: 64,
+      },
+      rpcConnection
+    );
+
+    try {
+      await gpl_compression.methods
+        .migrateTreeConfig()
+        .accounts({
+          treeConfig: treeConfigPDA,
+          merkleTree,
+          authority: payer.publicKey,
+        })
+        .signers([payer])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain(
+        "Error Code: InvalidLegacyTreeConfig"
+      );
+    }
   });
 });
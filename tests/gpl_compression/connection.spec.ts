@@ -99,13 +99,14 @@ describe("Connection Compression", async () => {
     // increment the index
     const randomHash = randomBytes(32);
     await gpl_compression.methods
-      .createCompressedConnection()
+      .createCompressedConnection(0)
       .accounts({
         fromProfile: profilePDA,
         toProfile: testProfilePDA,
         treeConfig: treeConfigPDA,
         merkleTree,
         authority: payer.publicKey,
+        delegate: null,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
         logWrapperProgram: SPL_NOOP_PROGRAM_ID,
         gplCoreProgram: gpl_core.programId,
@@ -142,13 +143,14 @@ describe("Connection Compression", async () => {
     let index = 0;
     const randomHash = randomBytes(32);
     await gpl_compression.methods
-      .createCompressedConnection()
+      .createCompressedConnection(0)
       .accounts({
         fromProfile: profilePDA,
         toProfile: testProfilePDA,
         treeConfig: treeConfigPDA,
         merkleTree,
         authority: payer.publicKey,
+        delegate: null,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
         logWrapperProgram: SPL_NOOP_PROGRAM_ID,
         gplCoreProgram: gpl_core.programId,
@@ -186,7 +188,7 @@ describe("Connection Compression", async () => {
     });
 
     await gpl_compression.methods
-      .deleteCompressedConnection(proof.root, index)
+      .deleteCompressedConnection(0, proof.root, index)
       .accounts({
         fromProfile: profilePDA,
         toProfile: testProfilePDA,
@@ -22,6 +22,8 @@ import { Keypair, PublicKey } from "@solana/web3.js";
 
 import randomBytes from "randombytes";
 
+import { expect } from "chai";
+
 import { faker } from "@faker-js/faker";
 
 anchor.setProvider(anchor.AnchorProvider.env());
@@ -80,6 +82,7 @@ describe("Post Compression", async () => {
         treeConfig: treeConfigPDA,
         merkleTree,
         authority: payer.publicKey,
+        delegate: null,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
         logWrapperProgram: SPL_NOOP_PROGRAM_ID,
         gplCoreProgram: gpl_core.programId,
@@ -123,6 +126,7 @@ describe("Post Compression", async () => {
         treeConfig: treeConfigPDA,
         merkleTree,
         authority: payer.publicKey,
+        delegate: null,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
         logWrapperProgram: SPL_NOOP_PROGRAM_ID,
         gplCoreProgram: gpl_core.programId,
@@ -212,6 +216,7 @@ describe("Post Compression", async () => {
         treeConfig: treeConfigPDA,
         merkleTree,
         authority: payer.publicKey,
+        delegate: null,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
         logWrapperProgram: SPL_NOOP_PROGRAM_ID,
         gplCoreProgram: gpl_core.programId,
@@ -274,4 +279,121 @@ describe("Post Compression", async () => {
     const newPostLeaf = Buffer.from(Array(32).fill(0));
     offChainTree.updateLeaf(index, newPostLeaf);
   });
+
+  it("should emit the leaf index and root of a compressed post", async () => {
+    const eventParser = new anchor.EventParser(
+      gpl_compression.programId,
+      new anchor.BorshCoder(gpl_compression.idl)
+    );
+    for (const expectedIndex of [0, 1]) {
+      const signature = await gpl_compression.methods
+        .createCompressedPost("https://example.com", randomBytes(32))
+        .accounts({
+          profile: profilePDA,
+          treeConfig: treeConfigPDA,
+          merkleTree,
+          authority: payer.publicKey,
+          delegate: null,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          logWrapperProgram: SPL_NOOP_PROGRAM_ID,
+          gplCoreProgram: gpl_core.programId,
+        })
+        .signers([payer])
+        .rpc({ commitment: "confirmed" });
+
+      const tx = await rpcConnection.getTransaction(signature, {
+        commitment: "confirmed",
+      });
+      const events = [...eventParser.parseLogs(tx.meta.logMessages)];
+      const postNew = events.find((e) => e.name === "CompressedPostNew");
+      expect(postNew.data.index).to.equal(expectedIndex);
+
+      const treeData = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+        rpcConnection,
+        merkleTree,
+        "confirmed"
+      );
+      const root = Buffer.from(postNew.data.root as number[]);
+      expect(root.toString("hex")).to.equal(
+        treeData.getCurrentRoot().toString("hex")
+      );
+    }
+  });
+
+  describe("Tree modes", async () => {
+    let writer: Keypair;
+    let writerProfilePDA: PublicKey;
+    let delegate: Keypair;
+
+    const createPost = (delegateSigner: Keypair | null) =>
+      gpl_compression.methods
+        .createCompressedPost("https://example.com", randomBytes(32))
+        .accounts({
+          profile: writerProfilePDA,
+          treeConfig: treeConfigPDA,
+          merkleTree,
+          authority: writer.publicKey,
+          delegate: delegateSigner ? delegateSigner.publicKey : null,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          logWrapperProgram: SPL_NOOP_PROGRAM_ID,
+          gplCoreProgram: gpl_core.programId,
+        })
+        .signers(delegateSigner ? [writer, delegateSigner] : [writer])
+        .rpc();
+
+    beforeEach(async () => {
+      writer = anchor.web3.Keypair.generate();
+      delegate = anchor.web3.Keypair.generate();
+      await airdrop(writer.publicKey);
+      const screenName = await createGumDomain(
+        await createGumTld(),
+        faker.internet.userName(),
+        writer
+      );
+      const profileTx = gpl_core.methods
+        .createProfile(randomBytes(32), "https://example.com")
+        .accounts({ authority: writer.publicKey, screenName });
+      writerProfilePDA = (await profileTx.pubkeys()).profile;
+      await profileTx.signers([writer]).rpc();
+    });
+
+    it("should only let the authority and the delegates write to a restricted tree", async () => {
+      for (const delegateSigner of [null, delegate]) {
+        try {
+          await createPost(delegateSigner);
+          expect.fail("should have failed");
+        } catch (error: any) {
+          expect(error.toString()).to.contain(
+            "Error Code: UnauthorizedTreeWriter"
+          );
+        }
+      }
+
+      await gpl_compression.methods
+        .updateTreeConfig({ restricted: {} }, [delegate.publicKey])
+        .accounts({ treeConfig: treeConfigPDA, authority: payer.publicKey })
+        .signers([payer])
+        .rpc();
+
+      try {
+        await createPost(null);
+        expect.fail("should have failed");
+      } catch (error: any) {
+        expect(error.toString()).to.contain(
+          "Error Code: UnauthorizedTreeWriter"
+        );
+      }
+      await createPost(delegate);
+    });
+
+    it("should let anyone write to a public tree", async () => {
+      await gpl_compression.methods
+        .updateTreeConfig({ public: {} }, [])
+        .accounts({ treeConfig: treeConfigPDA, authority: payer.publicKey })
+        .signers([payer])
+        .rpc();
+
+      await createPost(null);
+    });
+  });
 });
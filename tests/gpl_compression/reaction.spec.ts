@@ -35,6 +35,8 @@ describe("Reaction Compression", async () => {
 
   let profilePDA: PublicKey;
   let postPDA: PublicKey;
+  let postRandomHash: Buffer;
+  const postMetadataUri = "https://example.com";
   let emoji = "👍";
 
   beforeEach(async () => {
@@ -70,32 +72,56 @@ describe("Reaction Compression", async () => {
     profilePDA = profilePubKeys.profile as anchor.web3.PublicKey;
     await profileTx.signers([payer]).rpc();
 
-    // Create a post
-    const postRandomHash = randomBytes(32);
-    const metadataUri = "https://example.com";
-    const post = gpl_core.methods
-      // @ts-ignore
-      .createPost(metadataUri, postRandomHash)
+    // Create a compressed post
+    postRandomHash = randomBytes(32);
+    const postSeeds = [Buffer.from("post"), postRandomHash];
+    [postPDA] = await PublicKey.findProgramAddress(
+      postSeeds,
+      gpl_core.programId
+    );
+
+    await gpl_compression.methods
+      .createCompressedPost(postMetadataUri, postRandomHash)
       .accounts({
         profile: profilePDA,
+        treeConfig: treeConfigPDA,
+        merkleTree,
         authority: payer.publicKey,
-        sessionToken: null,
-      });
-    const postPubKeys = await post.pubkeys();
-    postPDA = postPubKeys.post as anchor.web3.PublicKey;
-    await post.signers([payer]).rpc();
+        delegate: null,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        logWrapperProgram: SPL_NOOP_PROGRAM_ID,
+        gplCoreProgram: gpl_core.programId,
+      })
+      .signers([payer])
+      .rpc();
+
+    const postData = {
+      profile: profilePDA,
+      metadataUri: postMetadataUri,
+      randomHash: postRandomHash,
+      replyTo: null,
+    };
+
+    const postLeaf = await to_leaf(merkleTree, "Post", postData, postSeeds);
+    offChainTree.updateLeaf(0, postLeaf);
   });
 
   it("should create a compressed reaction", async () => {
     const postProof = offChainTree.getProof(0);
+    const postRemainingAccounts = postProof.proof.map((p) => {
+      return { pubkey: new PublicKey(p), isWritable: false, isSigner: false };
+    });
 
     await gpl_compression.methods
       .createCompressedReaction(
         //@ts-ignore
         postPDA,
         emoji,
+        profilePDA,
+        postMetadataUri,
+        postRandomHash,
+        null,
         postProof.root,
-        postProof.leaf,
         0
       )
       .accounts({
@@ -105,10 +131,12 @@ describe("Reaction Compression", async () => {
         targetTreeConfig: treeConfigPDA,
         targetMerkleTree: merkleTree,
         authority: payer.publicKey,
+        delegate: null,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
         logWrapperProgram: SPL_NOOP_PROGRAM_ID,
         gplCoreProgram: gpl_core.programId,
       })
+      .remainingAccounts(postRemainingAccounts)
       .signers([payer])
       .rpc();
 
@@ -139,20 +167,25 @@ describe("Reaction Compression", async () => {
       reactionSeeds
     );
 
-    offChainTree.updateLeaf(0, reactionLeaf);
+    offChainTree.updateLeaf(1, reactionLeaf);
   });
 
   it("should create and delete a compressed reaction", async () => {
-    let index = 0;
-    const postProof = offChainTree.getProof(index);
+    const postProof = offChainTree.getProof(0);
+    const postRemainingAccounts = postProof.proof.map((p) => {
+      return { pubkey: new PublicKey(p), isWritable: false, isSigner: false };
+    });
     await gpl_compression.methods
       .createCompressedReaction(
         //@ts-ignore
         postPDA,
         emoji,
+        profilePDA,
+        postMetadataUri,
+        postRandomHash,
+        null,
         postProof.root,
-        postProof.leaf,
-        index
+        0
       )
       .accounts({
         fromProfile: profilePDA,
@@ -161,10 +194,12 @@ describe("Reaction Compression", async () => {
         targetTreeConfig: treeConfigPDA,
         targetMerkleTree: merkleTree,
         authority: payer.publicKey,
+        delegate: null,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
         logWrapperProgram: SPL_NOOP_PROGRAM_ID,
         gplCoreProgram: gpl_core.programId,
       })
+      .remainingAccounts(postRemainingAccounts)
       .signers([payer])
       .rpc();
 
@@ -195,6 +230,7 @@ describe("Reaction Compression", async () => {
       reactionSeeds
     );
 
+    const index = 1;
     offChainTree.updateLeaf(index, reactionLeaf);
 
     const proof = offChainTree.getProof(index);
@@ -221,4 +257,43 @@ describe("Reaction Compression", async () => {
     const newConnectionLeaf = Buffer.from(Array(32).fill(0));
     offChainTree.updateLeaf(index, newConnectionLeaf);
   });
+
+  it("should not create a compressed reaction with the wrong post fields", async () => {
+    const postProof = offChainTree.getProof(0);
+    const postRemainingAccounts = postProof.proof.map((p) => {
+      return { pubkey: new PublicKey(p), isWritable: false, isSigner: false };
+    });
+    try {
+      await gpl_compression.methods
+        .createCompressedReaction(
+          //@ts-ignore
+          postPDA,
+          emoji,
+          profilePDA,
+          postMetadataUri,
+          randomBytes(32),
+          null,
+          postProof.root,
+          0
+        )
+        .accounts({
+          fromProfile: profilePDA,
+          treeConfig: treeConfigPDA,
+          merkleTree,
+          targetTreeConfig: treeConfigPDA,
+          targetMerkleTree: merkleTree,
+          authority: payer.publicKey,
+          delegate: null,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          logWrapperProgram: SPL_NOOP_PROGRAM_ID,
+          gplCoreProgram: gpl_core.programId,
+        })
+        .remainingAccounts(postRemainingAccounts)
+        .signers([payer])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: InvalidTargetPost");
+    }
+  });
 });
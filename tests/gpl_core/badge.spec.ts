@@ -0,0 +1,790 @@
+import * as anchor from "@project-serum/anchor";
+import randombytes from "randombytes";
+import { expect } from "chai";
+import { GplCore } from "../../target/types/gpl_core";
+import {
+  airdrop,
+  createGumDomain,
+  createGumTld,
+  waitForEvent,
+} from "../utils";
+
+const program = anchor.workspace.GplCore as anchor.Program<GplCore>;
+
+anchor.setProvider(anchor.AnchorProvider.env());
+
+const provider = anchor.getProvider();
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new anchor.web3.PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+// Loaded by Anchor.toml from tests/fixtures/legacy_schema.json, a schema in the
+// layout from before official schemas, created by the legacy admin key
+const legacySchemaPDA = new anchor.web3.PublicKey(
+  "JCYq1Eru78x3X5wTtKL7PNZZcZMX2HJBv9gFdo2t5dQJ"
+);
+
+describe("Badge", async () => {
+  let protocolConfigPDA: anchor.web3.PublicKey;
+  let issuerPDA: anchor.web3.PublicKey;
+  let schemaPDA: anchor.web3.PublicKey;
+  let profilePDA: anchor.web3.PublicKey;
+  let badgePDA: anchor.web3.PublicKey;
+
+  before(async () => {
+    const gumTld = await createGumTld();
+    const screenName = await createGumDomain(gumTld, "badgeholder");
+    const profile = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({ screenName });
+    profilePDA = (await profile.pubkeys()).profile as anchor.web3.PublicKey;
+    await profile.rpc();
+
+    const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    );
+    // The provider wallet is the upgrade authority of the program
+    const config = program.methods
+      .initializeProtocolConfig([provider.publicKey], 1, [provider.publicKey])
+      .accounts({ program: program.programId, programData });
+    protocolConfigPDA = (await config.pubkeys())
+      .protocolConfig as anchor.web3.PublicKey;
+    await config.rpc();
+  });
+
+  it("should create an issuer", async () => {
+    const issuer = program.methods.createIssuer();
+    issuerPDA = (await issuer.pubkeys()).issuer as anchor.web3.PublicKey;
+    await issuer.rpc();
+
+    const issuerAccount = await program.account.issuer.fetch(issuerPDA);
+    expect(issuerAccount.authority.toBase58()).to.equal(
+      provider.publicKey.toBase58()
+    );
+    expect(issuerAccount.verified).to.equal(false);
+  });
+
+  it("should not verify an issuer without being a verifier", async () => {
+    const randomUser = anchor.web3.Keypair.generate();
+    await airdrop(randomUser.publicKey);
+    try {
+      await program.methods
+        .verifyIssuer()
+        .accounts({
+          issuer: issuerPDA,
+          protocolConfig: protocolConfigPDA,
+          signer: randomUser.publicKey,
+        })
+        .signers([randomUser])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: InvalidSignerToVerify");
+    }
+  });
+
+  it("should verify an issuer", async () => {
+    const issuerVerified = waitForEvent(program, "IssuerVerified");
+    await program.methods
+      .verifyIssuer()
+      .accounts({
+        issuer: issuerPDA,
+        protocolConfig: protocolConfigPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    const issuerAccount = await program.account.issuer.fetch(issuerPDA);
+    expect(issuerAccount.verified).to.equal(true);
+
+    const event = await issuerVerified;
+    expect(event.issuer.toBase58()).to.equal(issuerPDA.toBase58());
+    expect(event.verifier.toBase58()).to.equal(provider.publicKey.toBase58());
+  });
+
+  it("should update the verifiers with the admin signatures", async () => {
+    const verifier = anchor.web3.Keypair.generate();
+    await program.methods
+      .updateProtocolConfig(
+        [provider.publicKey],
+        1,
+        [provider.publicKey, verifier.publicKey]
+      )
+      .accounts({ protocolConfig: protocolConfigPDA })
+      .remainingAccounts([
+        { pubkey: provider.publicKey, isWritable: false, isSigner: true },
+      ])
+      .rpc();
+
+    const configAccount = await program.account.protocolConfig.fetch(
+      protocolConfigPDA
+    );
+    expect(configAccount.verifiers.length).to.equal(2);
+  });
+
+  it("should not update the protocol config without the admin signatures", async () => {
+    try {
+      await program.methods
+        .updateProtocolConfig([provider.publicKey], 1, [])
+        .accounts({ protocolConfig: protocolConfigPDA })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: NotEnoughAdminSigners");
+    }
+  });
+
+  it("should create an official schema with the admin signatures", async () => {
+    const schema = program.methods
+      .createSchema(
+        "https://example.com/official",
+        randombytes(32),
+        [],
+        false,
+        false
+      )
+      .accounts({ issuer: null, protocolConfig: protocolConfigPDA })
+      .remainingAccounts([
+        { pubkey: provider.publicKey, isWritable: false, isSigner: true },
+      ]);
+    const officialSchemaPDA = (await schema.pubkeys())
+      .schema as anchor.web3.PublicKey;
+    await schema.rpc();
+
+    const schemaAccount = await program.account.schema.fetch(officialSchemaPDA);
+    expect(schemaAccount.official).to.equal(true);
+    expect(schemaAccount.authority.toBase58()).to.equal(
+      protocolConfigPDA.toBase58()
+    );
+  });
+
+  it("should create a schema owned by the issuer", async () => {
+    const schema = program.methods
+      .createSchema(
+        "https://example.com/schema",
+        randombytes(32),
+        [{ name: "level", fieldType: { u64: {} } }],
+        false,
+        false
+      )
+      .accounts({ issuer: issuerPDA, protocolConfig: protocolConfigPDA });
+    schemaPDA = (await schema.pubkeys()).schema as anchor.web3.PublicKey;
+    await schema.rpc();
+
+    const schemaAccount = await program.account.schema.fetch(schemaPDA);
+    expect(schemaAccount.official).to.equal(false);
+    expect(schemaAccount.authority.toBase58()).to.equal(issuerPDA.toBase58());
+  });
+
+  it("should not update a legacy schema before it is migrated", async () => {
+    try {
+      await program.methods
+        .updateSchema("https://example.com/legacy-updated")
+        .accounts({
+          schema: legacySchemaPDA,
+          issuer: null,
+          protocolConfig: protocolConfigPDA,
+        })
+        .remainingAccounts([
+          { pubkey: provider.publicKey, isWritable: false, isSigner: true },
+        ])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: UnauthorizedSigner");
+    }
+  });
+
+  it("should not migrate a schema owned by an issuer", async () => {
+    try {
+      await program.methods
+        .migrateSchema()
+        .accounts({ schema: schemaPDA, protocolConfig: protocolConfigPDA })
+        .remainingAccounts([
+          { pubkey: provider.publicKey, isWritable: false, isSigner: true },
+        ])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: InvalidLegacySchema");
+    }
+  });
+
+  it("should migrate a legacy schema into an official schema", async () => {
+    await program.methods
+      .migrateSchema()
+      .accounts({ schema: legacySchemaPDA, protocolConfig: protocolConfigPDA })
+      .remainingAccounts([
+        { pubkey: provider.publicKey, isWritable: false, isSigner: true },
+      ])
+      .rpc();
+
+    let schemaAccount = await program.account.schema.fetch(legacySchemaPDA);
+    expect(schemaAccount.official).to.equal(true);
+    expect(schemaAccount.authority.toBase58()).to.equal(
+      protocolConfigPDA.toBase58()
+    );
+    expect(schemaAccount.metadataUri).to.equal("https://example.com/legacy");
+    expect(schemaAccount.fields.length).to.equal(0);
+
+    // The admins manage it like any other official schema
+    await program.methods
+      .updateSchema("https://example.com/legacy-updated")
+      .accounts({
+        schema: legacySchemaPDA,
+        issuer: null,
+        protocolConfig: protocolConfigPDA,
+      })
+      .remainingAccounts([
+        { pubkey: provider.publicKey, isWritable: false, isSigner: true },
+      ])
+      .rpc();
+    schemaAccount = await program.account.schema.fetch(legacySchemaPDA);
+    expect(schemaAccount.metadataUri).to.equal(
+      "https://example.com/legacy-updated"
+    );
+  });
+
+  it("should create a badge", async () => {
+    const badgeNew = waitForEvent(program, "BadgeNew");
+    const badge = program.methods
+      .createBadge(
+        "https://example.com/badge",
+        null,
+        new anchor.BN(3).toArrayLike(Buffer, "le", 8)
+      )
+      .accounts({
+        issuer: issuerPDA,
+        holder: profilePDA,
+        schema: schemaPDA,
+        updateAuthority: null,
+      });
+    badgePDA = (await badge.pubkeys()).badge as anchor.web3.PublicKey;
+    await badge.rpc();
+
+    const badgeAccount = await program.account.badge.fetch(badgePDA);
+    expect(badgeAccount.holder.toBase58()).to.equal(profilePDA.toBase58());
+    expect(badgeAccount.revoked).to.equal(false);
+    expect(
+      new anchor.BN(badgeAccount.attributes, "le").toNumber()
+    ).to.equal(3);
+
+    const event = await badgeNew;
+    expect(event.badge.toBase58()).to.equal(badgePDA.toBase58());
+    expect(event.holder.toBase58()).to.equal(profilePDA.toBase58());
+    expect(event.schema.toBase58()).to.equal(schemaPDA.toBase58());
+    expect(event.pending).to.equal(false);
+  });
+
+  it("should not create a badge with attributes not matching the schema", async () => {
+    const screenName = await createGumDomain(
+      await createGumTld(),
+      "badgeholdertwo"
+    );
+    const profile = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({ screenName });
+    const holderPDA = (await profile.pubkeys())
+      .profile as anchor.web3.PublicKey;
+    await profile.rpc();
+
+    try {
+      await program.methods
+        .createBadge("https://example.com/badge", null, Buffer.from([1]))
+        .accounts({
+          issuer: issuerPDA,
+          holder: holderPDA,
+          schema: schemaPDA,
+          updateAuthority: null,
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: InvalidBadgeAttributes");
+    }
+  });
+
+  it("should assert a badge", async () => {
+    await program.methods
+      .assertBadge()
+      .accounts({
+        badge: badgePDA,
+        issuer: issuerPDA,
+        schema: schemaPDA,
+        holder: profilePDA,
+      })
+      .rpc();
+  });
+
+  it("should revoke a badge", async () => {
+    await program.methods
+      .revokeBadge()
+      .accounts({
+        badge: badgePDA,
+        issuer: issuerPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    const badgeAccount = await program.account.badge.fetch(badgePDA);
+    expect(badgeAccount.revoked).to.equal(true);
+  });
+
+  it("should not assert a revoked badge", async () => {
+    try {
+      await program.methods
+        .assertBadge()
+        .accounts({
+          badge: badgePDA,
+          issuer: issuerPDA,
+          schema: schemaPDA,
+          holder: profilePDA,
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: BadgeRevoked");
+    }
+  });
+
+  it("should not update a revoked badge", async () => {
+    try {
+      await program.methods
+        .updateBadge(
+          "https://example.com/badge/updated",
+          new anchor.BN(4).toArrayLike(Buffer, "le", 8)
+        )
+        .accounts({
+          badge: badgePDA,
+          issuer: issuerPDA,
+          schema: schemaPDA,
+          signer: provider.publicKey,
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: BadgeRevoked");
+    }
+  });
+
+  it("should not create a badge with an expiry in the past", async () => {
+    const screenName = await createGumDomain(
+      await createGumTld(),
+      "expiredholder"
+    );
+    const profile = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({ screenName });
+    const holderPDA = (await profile.pubkeys())
+      .profile as anchor.web3.PublicKey;
+    await profile.rpc();
+
+    const yesterday = Math.floor(Date.now() / 1000) - 24 * 60 * 60;
+    try {
+      await program.methods
+        .createBadge(
+          "https://example.com/badge",
+          new anchor.BN(yesterday),
+          new anchor.BN(3).toArrayLike(Buffer, "le", 8)
+        )
+        .accounts({
+          issuer: issuerPDA,
+          holder: holderPDA,
+          schema: schemaPDA,
+          updateAuthority: null,
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: InvalidBadgeExpiry");
+    }
+  });
+
+  it("should accept a badge of a schema requiring acceptance", async () => {
+    const schema = program.methods
+      .createSchema(
+        "https://example.com/consent",
+        randombytes(32),
+        [],
+        true,
+        false
+      )
+      .accounts({ issuer: issuerPDA, protocolConfig: protocolConfigPDA });
+    const consentSchemaPDA = (await schema.pubkeys())
+      .schema as anchor.web3.PublicKey;
+    await schema.rpc();
+
+    const badge = program.methods
+      .createBadge("https://example.com/badge", null, Buffer.from([]))
+      .accounts({
+        issuer: issuerPDA,
+        holder: profilePDA,
+        schema: consentSchemaPDA,
+        updateAuthority: null,
+      });
+    const pendingBadgePDA = (await badge.pubkeys())
+      .badge as anchor.web3.PublicKey;
+    await badge.rpc();
+
+    let badgeAccount = await program.account.badge.fetch(pendingBadgePDA);
+    expect(badgeAccount.pending).to.equal(true);
+
+    await program.methods
+      .acceptBadge()
+      .accounts({
+        badge: pendingBadgePDA,
+        holder: profilePDA,
+        sessionToken: null,
+      })
+      .rpc();
+
+    badgeAccount = await program.account.badge.fetch(pendingBadgePDA);
+    expect(badgeAccount.pending).to.equal(false);
+  });
+
+  it("should transfer a badge to another profile", async () => {
+    const schema = program.methods
+      .createSchema(
+        "https://example.com/transferable",
+        randombytes(32),
+        [],
+        false,
+        true
+      )
+      .accounts({ issuer: issuerPDA, protocolConfig: protocolConfigPDA });
+    const transferableSchemaPDA = (await schema.pubkeys())
+      .schema as anchor.web3.PublicKey;
+    await schema.rpc();
+
+    const badge = program.methods
+      .createBadge("https://example.com/badge", null, Buffer.from([]))
+      .accounts({
+        issuer: issuerPDA,
+        holder: profilePDA,
+        schema: transferableSchemaPDA,
+        updateAuthority: null,
+      });
+    const oldBadgePDA = (await badge.pubkeys()).badge as anchor.web3.PublicKey;
+    await badge.rpc();
+
+    // The new profile belongs to the same authority
+    const screenName = await createGumDomain(
+      await createGumTld(),
+      "badgeholdernew"
+    );
+    const profile = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({ screenName });
+    const newProfilePDA = (await profile.pubkeys())
+      .profile as anchor.web3.PublicKey;
+    await profile.rpc();
+
+    const transfer = program.methods.transferBadge().accounts({
+      badge: oldBadgePDA,
+      issuer: issuerPDA,
+      schema: transferableSchemaPDA,
+      fromHolder: profilePDA,
+      toHolder: newProfilePDA,
+      sessionToken: null,
+    });
+    const newBadgePDA = (await transfer.pubkeys())
+      .newBadge as anchor.web3.PublicKey;
+    await transfer.rpc();
+
+    const badgeAccount = await program.account.badge.fetch(newBadgePDA);
+    expect(badgeAccount.holder.toBase58()).to.equal(newProfilePDA.toBase58());
+    try {
+      await program.account.badge.fetch(oldBadgePDA);
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain(
+        `Account does not exist or has no data ${oldBadgePDA.toString()}`
+      );
+    }
+  });
+
+  it("should unverify an issuer", async () => {
+    const issuerAuthority = anchor.web3.Keypair.generate();
+    await airdrop(issuerAuthority.publicKey);
+    const issuer = program.methods
+      .createIssuer()
+      .accounts({ authority: issuerAuthority.publicKey })
+      .signers([issuerAuthority]);
+    const unverifiedIssuerPDA = (await issuer.pubkeys())
+      .issuer as anchor.web3.PublicKey;
+    await issuer.rpc();
+
+    await program.methods
+      .verifyIssuer()
+      .accounts({
+        issuer: unverifiedIssuerPDA,
+        protocolConfig: protocolConfigPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    const schema = program.methods
+      .createSchema(
+        "https://example.com/unverified",
+        randombytes(32),
+        [],
+        false,
+        false
+      )
+      .accounts({
+        issuer: unverifiedIssuerPDA,
+        protocolConfig: protocolConfigPDA,
+        authority: issuerAuthority.publicKey,
+      })
+      .signers([issuerAuthority]);
+    const unverifiedSchemaPDA = (await schema.pubkeys())
+      .schema as anchor.web3.PublicKey;
+    await schema.rpc();
+
+    const badge = program.methods
+      .createBadge("https://example.com/unverified", null, Buffer.from([]))
+      .accounts({
+        issuer: unverifiedIssuerPDA,
+        holder: profilePDA,
+        schema: unverifiedSchemaPDA,
+        updateAuthority: null,
+        authority: issuerAuthority.publicKey,
+      })
+      .signers([issuerAuthority]);
+    const issuedBadgePDA = (await badge.pubkeys())
+      .badge as anchor.web3.PublicKey;
+    await badge.rpc();
+
+    await program.methods
+      .unverifyIssuer()
+      .accounts({
+        issuer: unverifiedIssuerPDA,
+        protocolConfig: protocolConfigPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    const issuerAccount = await program.account.issuer.fetch(
+      unverifiedIssuerPDA
+    );
+    expect(issuerAccount.verified).to.equal(false);
+
+    const screenName = await createGumDomain(
+      await createGumTld(),
+      "badgeholderthree"
+    );
+    const profile = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({ screenName });
+    const holderPDA = (await profile.pubkeys())
+      .profile as anchor.web3.PublicKey;
+    await profile.rpc();
+
+    try {
+      await program.methods
+        .createBadge("https://example.com/unverified", null, Buffer.from([]))
+        .accounts({
+          issuer: unverifiedIssuerPDA,
+          holder: holderPDA,
+          schema: unverifiedSchemaPDA,
+          updateAuthority: null,
+          authority: issuerAuthority.publicKey,
+        })
+        .signers([issuerAuthority])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: UnverifiedIssuer");
+    }
+
+    // The badges issued before are kept but no longer count
+    try {
+      await program.methods
+        .assertBadge()
+        .accounts({
+          badge: issuedBadgePDA,
+          issuer: unverifiedIssuerPDA,
+          schema: unverifiedSchemaPDA,
+          holder: profilePDA,
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: UnverifiedIssuer");
+    }
+  });
+
+  it("should suspend an issuer", async () => {
+    const issuerAuthority = anchor.web3.Keypair.generate();
+    await airdrop(issuerAuthority.publicKey);
+    const issuer = program.methods
+      .createIssuer()
+      .accounts({ authority: issuerAuthority.publicKey })
+      .signers([issuerAuthority]);
+    const suspendedIssuerPDA = (await issuer.pubkeys())
+      .issuer as anchor.web3.PublicKey;
+    await issuer.rpc();
+
+    await program.methods
+      .suspendIssuer()
+      .accounts({
+        issuer: suspendedIssuerPDA,
+        protocolConfig: protocolConfigPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    const issuerAccount = await program.account.issuer.fetch(
+      suspendedIssuerPDA
+    );
+    expect(issuerAccount.suspended).to.equal(true);
+
+    try {
+      await program.methods
+        .verifyIssuer()
+        .accounts({
+          issuer: suspendedIssuerPDA,
+          protocolConfig: protocolConfigPDA,
+          signer: provider.publicKey,
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: SuspendedIssuer");
+    }
+  });
+
+  it("should let the holder burn a badge of a suspended issuer", async () => {
+    const issuerAuthority = anchor.web3.Keypair.generate();
+    await airdrop(issuerAuthority.publicKey);
+    const issuer = program.methods
+      .createIssuer()
+      .accounts({ authority: issuerAuthority.publicKey })
+      .signers([issuerAuthority]);
+    const spamIssuerPDA = (await issuer.pubkeys())
+      .issuer as anchor.web3.PublicKey;
+    await issuer.rpc();
+
+    await program.methods
+      .verifyIssuer()
+      .accounts({
+        issuer: spamIssuerPDA,
+        protocolConfig: protocolConfigPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    const schema = program.methods
+      .createSchema(
+        "https://example.com/spam",
+        randombytes(32),
+        [],
+        false,
+        false
+      )
+      .accounts({
+        issuer: spamIssuerPDA,
+        protocolConfig: protocolConfigPDA,
+        authority: issuerAuthority.publicKey,
+      })
+      .signers([issuerAuthority]);
+    const spamSchemaPDA = (await schema.pubkeys())
+      .schema as anchor.web3.PublicKey;
+    await schema.rpc();
+
+    const badge = program.methods
+      .createBadge("https://example.com/spam", null, Buffer.from([]))
+      .accounts({
+        issuer: spamIssuerPDA,
+        holder: profilePDA,
+        schema: spamSchemaPDA,
+        updateAuthority: null,
+        authority: issuerAuthority.publicKey,
+      })
+      .signers([issuerAuthority]);
+    const spamBadgePDA = (await badge.pubkeys()).badge as anchor.web3.PublicKey;
+    await badge.rpc();
+
+    await program.methods
+      .suspendIssuer()
+      .accounts({
+        issuer: spamIssuerPDA,
+        protocolConfig: protocolConfigPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    // The provider wallet is the authority of the holder profile
+    await program.methods
+      .burnBadge()
+      .accounts({
+        badge: spamBadgePDA,
+        holder: profilePDA,
+        issuer: spamIssuerPDA,
+        schema: spamSchemaPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    const badgeAccount = await program.account.badge.fetchNullable(
+      spamBadgePDA
+    );
+    expect(badgeAccount).to.be.null;
+  });
+
+  it("should delete an issuer once its suspension is lifted", async () => {
+    const issuerAuthority = anchor.web3.Keypair.generate();
+    await airdrop(issuerAuthority.publicKey);
+    const issuer = program.methods
+      .createIssuer()
+      .accounts({ authority: issuerAuthority.publicKey })
+      .signers([issuerAuthority]);
+    const suspendedIssuerPDA = (await issuer.pubkeys())
+      .issuer as anchor.web3.PublicKey;
+    await issuer.rpc();
+
+    await program.methods
+      .suspendIssuer()
+      .accounts({
+        issuer: suspendedIssuerPDA,
+        protocolConfig: protocolConfigPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .unsuspendIssuer()
+      .accounts({
+        issuer: suspendedIssuerPDA,
+        protocolConfig: protocolConfigPDA,
+        signer: provider.publicKey,
+      })
+      .rpc();
+
+    const issuerAccount = await program.account.issuer.fetch(
+      suspendedIssuerPDA
+    );
+    expect(issuerAccount.suspended).to.equal(false);
+    expect(issuerAccount.verified).to.equal(false);
+
+    await program.methods
+      .deleteIssuer()
+      .accounts({
+        issuer: suspendedIssuerPDA,
+        authority: issuerAuthority.publicKey,
+      })
+      .signers([issuerAuthority])
+      .rpc();
+
+    const deletedIssuer = await program.account.issuer.fetchNullable(
+      suspendedIssuerPDA
+    );
+    expect(deletedIssuer).to.be.null;
+  });
+});
@@ -0,0 +1,200 @@
+import * as anchor from "@project-serum/anchor";
+import randombytes from "randombytes";
+import { expect } from "chai";
+import { GplCore } from "../../target/types/gpl_core";
+import {
+  airdrop,
+  createGumDomain,
+  createGumTld,
+  findBlockPDA,
+  findConnectionPDA,
+} from "../utils";
+
+const program = anchor.workspace.GplCore as anchor.Program<GplCore>;
+
+anchor.setProvider(anchor.AnchorProvider.env());
+
+const provider = anchor.getProvider();
+
+describe("Block", async () => {
+  let testUser: anchor.web3.Keypair;
+  let profilePDA: anchor.web3.PublicKey;
+  let testProfilePDA: anchor.web3.PublicKey;
+  let postPDA: anchor.web3.PublicKey;
+  let blockPDA: anchor.web3.PublicKey;
+
+  before(async () => {
+    const gumTld = await createGumTld();
+
+    // Create a profile
+    const screenName = await createGumDomain(gumTld, "blockerprofile");
+    const profileTx = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({ screenName });
+    profilePDA = (await profileTx.pubkeys()).profile as anchor.web3.PublicKey;
+    await profileTx.rpc();
+
+    // Create a post
+    const post = program.methods
+      .createPost("This is a test post", randombytes(32), [], [], null)
+      .accounts({ profile: profilePDA, sessionToken: null });
+    postPDA = (await post.pubkeys()).post as anchor.web3.PublicKey;
+    await post.rpc();
+
+    // Create a testUser with a profile
+    testUser = anchor.web3.Keypair.generate();
+    await airdrop(testUser.publicKey);
+    const testScreenName = await createGumDomain(
+      gumTld,
+      "blockedprofile",
+      testUser
+    );
+    const testProfile = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({
+        payer: testUser.publicKey,
+        authority: testUser.publicKey,
+        screenName: testScreenName,
+      })
+      .signers([testUser]);
+    testProfilePDA = (await testProfile.pubkeys())
+      .profile as anchor.web3.PublicKey;
+    await testProfile.rpc();
+  });
+
+  it("should block a profile", async () => {
+    const block = program.methods.createBlock().accounts({
+      blocker: profilePDA,
+      blocked: testProfilePDA,
+      sessionToken: null,
+    });
+    blockPDA = (await block.pubkeys()).block as anchor.web3.PublicKey;
+    await block.rpc();
+
+    const blockAccount = await program.account.block.fetch(blockPDA);
+    expect(blockAccount.blocker.toBase58()).to.equal(profilePDA.toBase58());
+    expect(blockAccount.blocked.toBase58()).to.equal(testProfilePDA.toBase58());
+  });
+
+  it("should not react to a post of a blocking profile", async () => {
+    try {
+      await program.methods
+        .createReaction("👍")
+        .accounts({
+          toPost: postPDA,
+          fromProfile: testProfilePDA,
+          block: blockPDA,
+          reactionSet: null,
+          sessionToken: null,
+          payer: testUser.publicKey,
+          authority: testUser.publicKey,
+        })
+        .signers([testUser])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: BlockedByProfile");
+    }
+  });
+
+  it("should not react to a post of a blocking profile without its block", async () => {
+    try {
+      await program.methods
+        .createReaction("👍")
+        .accounts({
+          toPost: postPDA,
+          fromProfile: testProfilePDA,
+          // Only the block of the post author is accepted
+          block: findBlockPDA(testProfilePDA, profilePDA),
+          reactionSet: null,
+          sessionToken: null,
+          payer: testUser.publicKey,
+          authority: testUser.publicKey,
+        })
+        .signers([testUser])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: ConstraintSeeds");
+    }
+  });
+
+  it("should not connect to a blocking profile", async () => {
+    try {
+      await program.methods
+        .createConnection(0)
+        .accounts({
+          fromProfile: testProfilePDA,
+          toProfile: profilePDA,
+          connection: findConnectionPDA(testProfilePDA, profilePDA, 0),
+          block: blockPDA,
+          sessionToken: null,
+          payer: testUser.publicKey,
+          authority: testUser.publicKey,
+        })
+        .signers([testUser])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: BlockedByProfile");
+    }
+  });
+
+  it("should not connect to a blocking profile in a batch", async () => {
+    const connectionPDA = findConnectionPDA(testProfilePDA, profilePDA, 0);
+    try {
+      await program.methods
+        .createConnections(0)
+        .accounts({
+          fromProfile: testProfilePDA,
+          sessionToken: null,
+          payer: testUser.publicKey,
+          authority: testUser.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: profilePDA, isWritable: false, isSigner: false },
+          { pubkey: connectionPDA, isWritable: true, isSigner: false },
+          { pubkey: blockPDA, isWritable: false, isSigner: false },
+        ])
+        .signers([testUser])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: BlockedByProfile");
+    }
+  });
+
+  it("should unblock a profile", async () => {
+    await program.methods
+      .deleteBlock()
+      .accounts({
+        block: blockPDA,
+        blocker: profilePDA,
+        blocked: testProfilePDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    // The block PDA can still be passed once it is closed
+    const reaction = program.methods.createReaction("👍").accounts({
+      toPost: postPDA,
+      fromProfile: testProfilePDA,
+      block: blockPDA,
+      reactionSet: null,
+      sessionToken: null,
+      payer: testUser.publicKey,
+      authority: testUser.publicKey,
+    });
+    const reactionPDA = (await reaction.pubkeys())
+      .reaction as anchor.web3.PublicKey;
+    await reaction.signers([testUser]).rpc();
+
+    const reactionAccount = await program.account.reaction.fetch(reactionPDA);
+    expect(reactionAccount.fromProfile.toBase58()).to.equal(
+      testProfilePDA.toBase58()
+    );
+  });
+});
@@ -3,7 +3,13 @@ import randombytes from "randombytes";
 import { expect } from "chai";
 import { sendAndConfirmTransaction } from "@solana/web3.js";
 import { GplCore } from "../../target/types/gpl_core";
-import { new_session, airdrop, createGumTld, createGumDomain } from "../utils";
+import {
+  new_session,
+  airdrop,
+  createGumTld,
+  createGumDomain,
+  findBlockPDA,
+} from "../utils";
 
 const program = anchor.workspace.GplCore as anchor.Program<GplCore>;
 
@@ -38,7 +44,7 @@ describe("Comment", async () => {
     const postRandomHash = randombytes(32);
     const metadataUri = "This is a test post";
     const post = program.methods
-      .createPost(metadataUri, postRandomHash)
+      .createPost(metadataUri, postRandomHash, [], [], null)
       .accounts({ profile: profilePDA, sessionToken: null });
     const postPubKeys = await post.pubkeys();
     postPDA = postPubKeys.post as anchor.web3.PublicKey;
@@ -76,12 +82,13 @@ describe("Comment", async () => {
     // create a comment from fromProfilePDA to postPDA
     const commentTx = program.methods
       // @ts-ignore
-      .createComment("This is a test comment", randombytes(32))
+      .createComment("This is a test comment", randombytes(32), [], [])
       .accounts({
         replyTo: postPDA,
         profile: fromProfilePDA,
         authority: testUserKeypair.publicKey,
         sessionToken: null,
+        block: findBlockPDA(profilePDA, fromProfilePDA),
       });
 
     const commentPubkeys = await commentTx.pubkeys();
@@ -96,7 +103,7 @@ describe("Comment", async () => {
   it("should create a comment when a seperate fee payer is specified", async () => {
     const createComment = program.methods
       // @ts-ignore
-      .createComment("This is a test comment", randombytes(32))
+      .createComment("This is a test comment", randombytes(32), [], [])
       .accounts({
         payer: feePayer.publicKey,
         replyTo: postPDA,
@@ -104,6 +111,7 @@ describe("Comment", async () => {
         user: testUserPDA,
         authority: testUserKeypair.publicKey,
         sessionToken: null,
+        block: findBlockPDA(profilePDA, fromProfilePDA),
       });
     const pubKeys = await createComment.pubkeys();
     const commentPDA = pubKeys.post as anchor.web3.PublicKey;
@@ -114,6 +122,56 @@ describe("Comment", async () => {
     );
   });
 
+  it("should clean up a comment once its parent post is deleted", async () => {
+    const post = program.methods
+      .createPost("This post will be deleted", randombytes(32), [], [], null)
+      .accounts({ profile: profilePDA, sessionToken: null });
+    const parentPostPDA = (await post.pubkeys()).post as anchor.web3.PublicKey;
+    await post.rpc();
+
+    const commentTx = program.methods
+      // @ts-ignore
+      .createComment("This is a test comment", randombytes(32), [], [])
+      .accounts({
+        replyTo: parentPostPDA,
+        profile: fromProfilePDA,
+        authority: testUserKeypair.publicKey,
+        sessionToken: null,
+        block: findBlockPDA(profilePDA, fromProfilePDA),
+      });
+    const commentPDA = (await commentTx.pubkeys()).post as anchor.web3.PublicKey;
+    await commentTx.signers([testUserKeypair]).rpc();
+
+    await program.methods
+      .deletePost()
+      .accounts({
+        profile: profilePDA,
+        post: parentPostPDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .cleanupOrphanComment()
+      .accounts({
+        post: commentPDA,
+        replyTo: parentPostPDA,
+        profile: fromProfilePDA,
+        refundReceiver: testUserKeypair.publicKey,
+      })
+      .rpc();
+
+    try {
+      await program.account.post.fetch(commentPDA);
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain(
+        `Account does not exist or has no data ${commentPDA.toString()}`
+      );
+    }
+  });
+
   describe("Comment with session token", async () => {
     let sessionToken: anchor.web3.PublicKey;
     let sessionKeypair: anchor.web3.Keypair;
@@ -133,12 +191,13 @@ describe("Comment", async () => {
       // create a comment from fromProfilePDA to postPDA
       const commentTx = program.methods
         // @ts-ignore
-        .createComment("This is a test comment", randombytes(32))
+        .createComment("This is a test comment", randombytes(32), [], [])
         .accounts({
           replyTo: postPDA,
           profile: fromProfilePDA,
           authority: sessionKeypair.publicKey,
           sessionToken: sessionToken,
+          block: findBlockPDA(profilePDA, fromProfilePDA),
         });
 
       const commentPubkeys = await commentTx.pubkeys();
@@ -1,7 +1,12 @@
 import * as anchor from "@project-serum/anchor";
 import NodeWallet from "@project-serum/anchor/dist/cjs/nodewallet";
 import randombytes from "randombytes";
-import { airdrop, new_session } from "../utils";
+import {
+  airdrop,
+  findBlockPDA,
+  findConnectionPDA,
+  new_session,
+} from "../utils";
 import { expect } from "chai";
 import { sendAndConfirmTransaction } from "@solana/web3.js";
 import { GplCore } from "../../target/types/gpl_core";
@@ -68,10 +73,12 @@ describe("Connection", async () => {
   });
 
   it("should create a connection", async () => {
-    const connection = program.methods.createConnection().accounts({
+    const connection = program.methods.createConnection(0).accounts({
       fromProfile: profilePDA,
       toProfile: testProfilePDA,
+      connection: findConnectionPDA(profilePDA, testProfilePDA, 0),
       sessionToken: null,
+      block: findBlockPDA(testProfilePDA, profilePDA),
     });
     const pubKeys = await connection.pubkeys();
     connectionPDA = pubKeys.connection as anchor.web3.PublicKey;
@@ -110,11 +117,13 @@ describe("Connection", async () => {
   });
 
   it("should create a connection when a seperate fee payer is specified", async () => {
-    const createConnection = program.methods.createConnection().accounts({
+    const createConnection = program.methods.createConnection(0).accounts({
       payer: feePayer.publicKey,
       fromProfile: profilePDA,
       toProfile: testProfilePDA,
+      connection: findConnectionPDA(profilePDA, testProfilePDA, 0),
       sessionToken: null,
+      block: findBlockPDA(testProfilePDA, profilePDA),
     });
     const pubKeys = await createConnection.pubkeys();
     connectionPDA = pubKeys.connection as anchor.web3.PublicKey;
@@ -144,6 +153,227 @@ describe("Connection", async () => {
       .rpc();
   });
 
+  it("should create a connection of an application defined kind", async () => {
+    const kind = 200;
+    const connection = program.methods.createConnection(kind).accounts({
+      fromProfile: profilePDA,
+      toProfile: testProfilePDA,
+      connection: findConnectionPDA(profilePDA, testProfilePDA, kind),
+      sessionToken: null,
+      block: findBlockPDA(testProfilePDA, profilePDA),
+    });
+    const kindConnectionPDA = (await connection.pubkeys())
+      .connection as anchor.web3.PublicKey;
+    await connection.rpc();
+
+    const connectionAccount = await program.account.connection.fetch(
+      kindConnectionPDA
+    );
+    expect(connectionAccount.kind).to.equal(kind);
+
+    // Cleanup for next tests
+    await program.methods
+      .deleteConnection()
+      .accounts({
+        fromProfile: profilePDA,
+        toProfile: testProfilePDA,
+        connection: kindConnectionPDA,
+        sessionToken: null,
+        // @ts-ignore
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+  });
+
+  it("should not create a connection of a reserved kind", async () => {
+    try {
+      await program.methods
+        .createConnection(42)
+        .accounts({
+          fromProfile: profilePDA,
+          toProfile: testProfilePDA,
+          connection: findConnectionPDA(profilePDA, testProfilePDA, 42),
+          sessionToken: null,
+          block: findBlockPDA(testProfilePDA, profilePDA),
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: InvalidConnectionKind");
+    }
+  });
+
+  it("should not create a connection of the unused block kind", async () => {
+    try {
+      await program.methods
+        .createConnection(1)
+        .accounts({
+          fromProfile: profilePDA,
+          toProfile: testProfilePDA,
+          connection: findConnectionPDA(profilePDA, testProfilePDA, 1),
+          sessionToken: null,
+          block: findBlockPDA(testProfilePDA, profilePDA),
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: InvalidConnectionKind");
+    }
+  });
+
+  it("should keep follows at the legacy address and not delete them as legacy connections", async () => {
+    const [legacyConnectionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("connection"),
+        profilePDA.toBuffer(),
+        testProfilePDA.toBuffer(),
+      ],
+      program.programId
+    );
+    const followPDA = findConnectionPDA(profilePDA, testProfilePDA, 0);
+    expect(followPDA.toBase58()).to.equal(legacyConnectionPDA.toBase58());
+
+    await program.methods
+      .createConnection(0)
+      .accounts({
+        fromProfile: profilePDA,
+        toProfile: testProfilePDA,
+        connection: followPDA,
+        sessionToken: null,
+        block: findBlockPDA(testProfilePDA, profilePDA),
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .deleteLegacyConnection()
+        .accounts({
+          connection: legacyConnectionPDA,
+          fromProfile: profilePDA,
+          toProfile: testProfilePDA,
+          sessionToken: null,
+          refundReceiver: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain(
+        "Error Code: InvalidLegacyConnection"
+      );
+    }
+
+    // Cleanup for next tests
+    await program.methods
+      .deleteConnection()
+      .accounts({
+        fromProfile: profilePDA,
+        toProfile: testProfilePDA,
+        connection: followPDA,
+        sessionToken: null,
+        // @ts-ignore
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+  });
+
+  it("should create and delete connections in a batch", async () => {
+    // Create another profile to follow
+    const otherUser = anchor.web3.Keypair.generate();
+    await airdrop(otherUser.publicKey);
+    const gumTld = await createGumTld();
+    const otherScreenName = await createGumDomain(gumTld, "batch", otherUser);
+    const otherProfile = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({
+        payer: otherUser.publicKey,
+        authority: otherUser.publicKey,
+        screenName: otherScreenName,
+      })
+      .signers([otherUser]);
+    const otherProfilePDA = (await otherProfile.pubkeys())
+      .profile as anchor.web3.PublicKey;
+    await otherProfile.rpc();
+
+    const toProfiles = [testProfilePDA, otherProfilePDA];
+    const connectionPDAs = toProfiles.map((toProfile) =>
+      findConnectionPDA(profilePDA, toProfile, 0)
+    );
+
+    // Lamports sent to a connection address must not block its creation
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.publicKey,
+          toPubkey: connectionPDAs[0],
+          lamports: 1000,
+        })
+      )
+    );
+
+    const createConnections = (blockPDAs: anchor.web3.PublicKey[]) =>
+      program.methods
+        .createConnections(0)
+        .accounts({ fromProfile: profilePDA, sessionToken: null })
+        .remainingAccounts(
+          toProfiles.flatMap((toProfile, i) => [
+            { pubkey: toProfile, isWritable: false, isSigner: false },
+            { pubkey: connectionPDAs[i], isWritable: true, isSigner: false },
+            { pubkey: blockPDAs[i], isWritable: false, isSigner: false },
+          ])
+        )
+        .rpc();
+    const blockPDAs = toProfiles.map((toProfile) =>
+      findBlockPDA(toProfile, profilePDA)
+    );
+
+    // Every pair has to pass its block PDA, even when the block doesn't exist
+    try {
+      await createConnections([blockPDAs[0], program.programId]);
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain(
+        "Error Code: InvalidConnectionBatch"
+      );
+    }
+
+    await createConnections(blockPDAs);
+
+    for (const [i, pda] of connectionPDAs.entries()) {
+      const connectionAccount = await program.account.connection.fetch(pda);
+      expect(connectionAccount.toProfile.toBase58()).to.equal(
+        toProfiles[i].toBase58()
+      );
+    }
+
+    await program.methods
+      .deleteConnections()
+      .accounts({
+        fromProfile: profilePDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .remainingAccounts(
+        connectionPDAs.map((pda) => ({
+          pubkey: pda,
+          isWritable: true,
+          isSigner: false,
+        }))
+      )
+      .rpc();
+
+    for (const pda of connectionPDAs) {
+      try {
+        await program.account.connection.fetch(pda);
+      } catch (error: any) {
+        expect(error).to.be.an("error");
+        expect(error.toString()).to.contain(
+          `Account does not exist or has no data ${pda.toString()}`
+        );
+      }
+    }
+  });
+
   describe("Connection with session token", async () => {
     let sessionToken: anchor.web3.PublicKey;
     let sessionKeypair: anchor.web3.Keypair;
@@ -159,10 +389,12 @@ describe("Connection", async () => {
     });
 
     it("should create a connection", async () => {
-      const connection = program.methods.createConnection().accounts({
+      const connection = program.methods.createConnection(0).accounts({
         fromProfile: profilePDA,
         toProfile: testProfilePDA,
+        connection: findConnectionPDA(profilePDA, testProfilePDA, 0),
         sessionToken: sessionToken,
+        block: findBlockPDA(testProfilePDA, profilePDA),
         authority: sessionKeypair.publicKey,
       });
       const pubKeys = await connection.pubkeys();
@@ -201,4 +433,124 @@ describe("Connection", async () => {
       }
     });
   });
+
+  describe("Connection to a private profile", async () => {
+    before(async () => {
+      await program.methods
+        .updateProfilePrivacy(true)
+        .accounts({ profile: testProfilePDA, authority: testUser.publicKey })
+        .signers([testUser])
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateProfilePrivacy(false)
+        .accounts({ profile: testProfilePDA, authority: testUser.publicKey })
+        .signers([testUser])
+        .rpc();
+    });
+
+    it("should not create a connection without approval", async () => {
+      try {
+        await program.methods
+          .createConnection(0)
+          .accounts({
+            fromProfile: profilePDA,
+            toProfile: testProfilePDA,
+            connection: findConnectionPDA(profilePDA, testProfilePDA, 0),
+            sessionToken: null,
+            block: findBlockPDA(testProfilePDA, profilePDA),
+          })
+          .rpc();
+        expect.fail("should have failed");
+      } catch (error: any) {
+        expect(error).to.be.an("error");
+        expect(error.toString()).to.contain("Error Code: ProfileIsPrivate");
+      }
+    });
+
+    it("should request and approve a connection", async () => {
+      const request = program.methods.requestConnection(0).accounts({
+        fromProfile: profilePDA,
+        toProfile: testProfilePDA,
+        connection: findConnectionPDA(profilePDA, testProfilePDA, 0),
+        sessionToken: null,
+        block: findBlockPDA(testProfilePDA, profilePDA),
+      });
+      connectionPDA = (await request.pubkeys())
+        .connection as anchor.web3.PublicKey;
+      await request.rpc();
+
+      let connectionAccount = await program.account.connection.fetch(
+        connectionPDA
+      );
+      expect(connectionAccount.pending).to.equal(true);
+
+      await program.methods
+        .approveConnection()
+        .accounts({
+          connection: connectionPDA,
+          fromProfile: profilePDA,
+          toProfile: testProfilePDA,
+          sessionToken: null,
+          authority: testUser.publicKey,
+        })
+        .signers([testUser])
+        .rpc();
+
+      connectionAccount = await program.account.connection.fetch(
+        connectionPDA
+      );
+      expect(connectionAccount.pending).to.equal(false);
+
+      // Cleanup for next tests
+      await program.methods
+        .deleteConnection()
+        .accounts({
+          fromProfile: profilePDA,
+          toProfile: testProfilePDA,
+          connection: connectionPDA,
+          sessionToken: null,
+          // @ts-ignore
+          refundReceiver: provider.wallet.publicKey,
+        })
+        .rpc();
+    });
+
+    it("should request and reject a connection", async () => {
+      const request = program.methods.requestConnection(0).accounts({
+        fromProfile: profilePDA,
+        toProfile: testProfilePDA,
+        connection: findConnectionPDA(profilePDA, testProfilePDA, 0),
+        sessionToken: null,
+        block: findBlockPDA(testProfilePDA, profilePDA),
+      });
+      connectionPDA = (await request.pubkeys())
+        .connection as anchor.web3.PublicKey;
+      await request.rpc();
+
+      await program.methods
+        .rejectConnection()
+        .accounts({
+          connection: connectionPDA,
+          fromProfile: profilePDA,
+          toProfile: testProfilePDA,
+          sessionToken: null,
+          authority: testUser.publicKey,
+          refundReceiver: provider.wallet.publicKey,
+        })
+        .signers([testUser])
+        .rpc();
+
+      try {
+        await program.account.connection.fetch(connectionPDA);
+      } catch (error: any) {
+        expect(error).to.be.an("error");
+        expect(error.toString()).to.contain(
+          `Account does not exist or has no data ${connectionPDA.toString()}`
+        );
+      }
+    });
+  });
 });
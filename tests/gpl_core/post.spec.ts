@@ -4,6 +4,15 @@ import { expect } from "chai";
 import { GplCore } from "../../target/types/gpl_core";
 import { createGumDomain, createGumTld } from "../utils";
 import { airdrop, new_session } from "../utils";
+import {
+  createMint,
+  createTokenAccount,
+  findMetadataPDA,
+  fixtureCollection,
+  spl_token,
+  unverifiedCollectionMint,
+  verifiedCollectionMint,
+} from "../utils";
 import { sendAndConfirmTransaction } from "@solana/web3.js";
 
 const program = anchor.workspace.GplCore as anchor.Program<GplCore>;
@@ -40,7 +49,7 @@ describe("Post", async () => {
     const randomHash = randombytes(32);
     const metadataUri = "This is a test post";
     const post = program.methods
-      .createPost(metadataUri, randomHash)
+      .createPost(metadataUri, randomHash, [], [], null)
       .accounts({ profile: profilePDA, sessionToken: null });
     const postPubKeys = await post.pubkeys();
     postPDA = postPubKeys.post as anchor.web3.PublicKey;
@@ -85,7 +94,7 @@ describe("Post", async () => {
     const randomHash = randombytes(32);
     const metadataUri = "This is a test post";
     const createPost = program.methods
-      .createPost(metadataUri, randomHash)
+      .createPost(metadataUri, randomHash, [], [], null)
       .accounts({
         payer: feePayer.publicKey,
         profile: profilePDA,
@@ -99,6 +108,385 @@ describe("Post", async () => {
     expect(postAccount.profile.toString()).is.equal(profilePDA.toString());
   });
 
+  it("should create a post with mentions and tags", async () => {
+    const randomHash = randombytes(32);
+    const metadataUri = "This is a test post with a mention";
+    const tag = randombytes(32);
+    const post = program.methods
+      .createPost(metadataUri, randomHash, [profilePDA], [tag], null)
+      .accounts({ profile: profilePDA, sessionToken: null })
+      .remainingAccounts([
+        { pubkey: profilePDA, isWritable: false, isSigner: false },
+      ]);
+    const postPubKeys = await post.pubkeys();
+    const mentionPostPDA = postPubKeys.post as anchor.web3.PublicKey;
+    await post.rpc();
+    const postAccount = await program.account.post.fetch(mentionPostPDA);
+    expect(postAccount.metadataUri).is.equal(metadataUri);
+  });
+
+  it("should not create a post when a mentioned profile is missing", async () => {
+    const randomHash = randombytes(32);
+    const metadataUri = "This is a test post with a mention";
+    try {
+      await program.methods
+        .createPost(metadataUri, randomHash, [profilePDA], [], null)
+        .accounts({ profile: profilePDA, sessionToken: null })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: InvalidMention");
+    }
+  });
+
+  it("should create a token gated post", async () => {
+    const mint = anchor.web3.Keypair.generate().publicKey;
+    const post = program.methods
+      .createPost("This is a gated post", randombytes(32), [], [], {
+        kind: { mint: {} },
+        key: mint,
+        minAmount: new anchor.BN(1),
+      })
+      .accounts({ profile: profilePDA, sessionToken: null });
+    const gatedPostPDA = (await post.pubkeys()).post as anchor.web3.PublicKey;
+    await post.rpc();
+    const postAccount = await program.account.post.fetch(gatedPostPDA);
+    expect(postAccount.gate.key.toBase58()).to.equal(mint.toBase58());
+    expect(postAccount.gate.minAmount.toNumber()).to.equal(1);
+  });
+
+  it("should not create a token gated post without a minimum amount", async () => {
+    try {
+      await program.methods
+        .createPost("This is a gated post", randombytes(32), [], [], {
+          kind: { collection: {} },
+          key: anchor.web3.Keypair.generate().publicKey,
+          minAmount: new anchor.BN(0),
+        })
+        .accounts({ profile: profilePDA, sessionToken: null })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: InvalidGate");
+    }
+  });
+
+  it("should not create a collection gated post above one token", async () => {
+    try {
+      await program.methods
+        .createPost("This is a gated post", randombytes(32), [], [], {
+          kind: { collection: {} },
+          key: anchor.web3.Keypair.generate().publicKey,
+          minAmount: new anchor.BN(2),
+        })
+        .accounts({ profile: profilePDA, sessionToken: null })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: InvalidGate");
+    }
+  });
+
+  describe("Gated post access", async () => {
+    let mint: anchor.web3.PublicKey;
+    let mintGatedPostPDA: anchor.web3.PublicKey;
+
+    const createGatedPost = async (
+      kind: any,
+      key: anchor.web3.PublicKey,
+      minAmount: number
+    ): Promise<anchor.web3.PublicKey> => {
+      const post = program.methods
+        .createPost("This is a gated post", randombytes(32), [], [], {
+          kind,
+          key,
+          minAmount: new anchor.BN(minAmount),
+        })
+        .accounts({ profile: profilePDA, sessionToken: null });
+      const gatedPostPDA = (await post.pubkeys())
+        .post as anchor.web3.PublicKey;
+      await post.rpc();
+      return gatedPostPDA;
+    };
+
+    before(async () => {
+      mint = await createMint();
+      mintGatedPostPDA = await createGatedPost({ mint: {} }, mint, 2);
+    });
+
+    it("should assert access to a token gated post", async () => {
+      const tokenAccount = await createTokenAccount(
+        mint,
+        provider.publicKey,
+        2
+      );
+      await program.methods
+        .assertAccess()
+        .accounts({
+          post: mintGatedPostPDA,
+          tokenAccount,
+          metadata: null,
+          holder: provider.publicKey,
+        })
+        .rpc();
+    });
+
+    it("should not assert access with a token of another mint", async () => {
+      const otherMint = await createMint();
+      const tokenAccount = await createTokenAccount(
+        otherMint,
+        provider.publicKey,
+        2
+      );
+      try {
+        await program.methods
+          .assertAccess()
+          .accounts({
+            post: mintGatedPostPDA,
+            tokenAccount,
+            metadata: null,
+            holder: provider.publicKey,
+          })
+          .rpc();
+        expect.fail("should have failed");
+      } catch (error: any) {
+        expect(error.toString()).to.contain("Error Code: AccessDenied");
+      }
+    });
+
+    it("should not assert access below the minimum amount", async () => {
+      const tokenAccount = await createTokenAccount(
+        mint,
+        provider.publicKey,
+        1
+      );
+      try {
+        await program.methods
+          .assertAccess()
+          .accounts({
+            post: mintGatedPostPDA,
+            tokenAccount,
+            metadata: null,
+            holder: provider.publicKey,
+          })
+          .rpc();
+        expect.fail("should have failed");
+      } catch (error: any) {
+        expect(error.toString()).to.contain("Error Code: AccessDenied");
+      }
+    });
+
+    it("should assert access to a collection gated post with a verified token", async () => {
+      const collectionMint = await createMint(verifiedCollectionMint);
+      const tokenAccount = await createTokenAccount(
+        collectionMint,
+        provider.publicKey,
+        1
+      );
+      const gatedPostPDA = await createGatedPost(
+        { collection: {} },
+        fixtureCollection,
+        1
+      );
+      await program.methods
+        .assertAccess()
+        .accounts({
+          post: gatedPostPDA,
+          tokenAccount,
+          metadata: findMetadataPDA(collectionMint),
+          holder: provider.publicKey,
+        })
+        .rpc();
+    });
+
+    it("should not assert access to a collection gated post with an unverified token", async () => {
+      const collectionMint = await createMint(unverifiedCollectionMint);
+      const tokenAccount = await createTokenAccount(
+        collectionMint,
+        provider.publicKey,
+        1
+      );
+      const gatedPostPDA = await createGatedPost(
+        { collection: {} },
+        fixtureCollection,
+        1
+      );
+      try {
+        await program.methods
+          .assertAccess()
+          .accounts({
+            post: gatedPostPDA,
+            tokenAccount,
+            metadata: findMetadataPDA(collectionMint),
+            holder: provider.publicKey,
+          })
+          .rpc();
+        expect.fail("should have failed");
+      } catch (error: any) {
+        expect(error.toString()).to.contain("Error Code: AccessDenied");
+      }
+    });
+  });
+
+  it("should tip a post in lamports", async () => {
+    const tipper = anchor.web3.Keypair.generate();
+    await airdrop(tipper.publicKey);
+    const gumTld = await createGumTld();
+    const screenName = await createGumDomain(gumTld, "tipperprofile", tipper);
+    const tipperProfile = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({
+        payer: tipper.publicKey,
+        authority: tipper.publicKey,
+        screenName,
+      })
+      .signers([tipper]);
+    const tipperProfilePDA = (await tipperProfile.pubkeys())
+      .profile as anchor.web3.PublicKey;
+    await tipperProfile.rpc();
+
+    // The provider wallet pays the fees, so only the tipper balance is exact
+    const balanceBefore = await provider.connection.getBalance(
+      tipper.publicKey
+    );
+    const amount = new anchor.BN(1_000_000);
+    await program.methods
+      .tipPost(amount)
+      .accounts({
+        post: postPDA,
+        fromProfile: tipperProfilePDA,
+        toProfile: profilePDA,
+        authority: tipper.publicKey,
+        recipient: provider.wallet.publicKey,
+        mint: null,
+        senderTokenAccount: null,
+        recipientTokenAccount: null,
+        tokenProgram: null,
+      })
+      .signers([tipper])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      tipper.publicKey
+    );
+    expect(balanceBefore - balanceAfter).to.equal(amount.toNumber());
+  });
+
+  describe("SPL token tips", async () => {
+    let tipper: anchor.web3.Keypair;
+    let tipperProfilePDA: anchor.web3.PublicKey;
+    let mint: anchor.web3.PublicKey;
+    let senderTokenAccount: anchor.web3.PublicKey;
+    let recipientTokenAccount: anchor.web3.PublicKey;
+
+    const tokenBalance = async (account: anchor.web3.PublicKey) =>
+      Number(
+        (await provider.connection.getTokenAccountBalance(account)).value.amount
+      );
+
+    before(async () => {
+      tipper = anchor.web3.Keypair.generate();
+      await airdrop(tipper.publicKey);
+      const gumTld = await createGumTld();
+      const screenName = await createGumDomain(
+        gumTld,
+        "spltipperprofile",
+        tipper
+      );
+      const tipperProfile = program.methods
+        .createProfile(randombytes(32), "https://example.com")
+        .accounts({
+          payer: tipper.publicKey,
+          authority: tipper.publicKey,
+          screenName,
+        })
+        .signers([tipper]);
+      tipperProfilePDA = (await tipperProfile.pubkeys())
+        .profile as anchor.web3.PublicKey;
+      await tipperProfile.rpc();
+
+      mint = await createMint();
+      senderTokenAccount = await createTokenAccount(mint, tipper.publicKey, 10);
+      recipientTokenAccount = await createTokenAccount(
+        mint,
+        provider.publicKey,
+        0
+      );
+    });
+
+    it("should tip a post in an SPL token", async () => {
+      await program.methods
+        .tipPost(new anchor.BN(4))
+        .accounts({
+          post: postPDA,
+          fromProfile: tipperProfilePDA,
+          toProfile: profilePDA,
+          authority: tipper.publicKey,
+          recipient: provider.publicKey,
+          mint,
+          senderTokenAccount,
+          recipientTokenAccount,
+          tokenProgram: spl_token.programId,
+        })
+        .signers([tipper])
+        .rpc();
+      expect(await tokenBalance(senderTokenAccount)).to.equal(6);
+      expect(await tokenBalance(recipientTokenAccount)).to.equal(4);
+    });
+
+    it("should not tip into a token account of another wallet", async () => {
+      const otherTokenAccount = await createTokenAccount(
+        mint,
+        anchor.web3.Keypair.generate().publicKey,
+        0
+      );
+      try {
+        await program.methods
+          .tipPost(new anchor.BN(1))
+          .accounts({
+            post: postPDA,
+            fromProfile: tipperProfilePDA,
+            toProfile: profilePDA,
+            authority: tipper.publicKey,
+            recipient: provider.publicKey,
+            mint,
+            senderTokenAccount,
+            recipientTokenAccount: otherTokenAccount,
+            tokenProgram: spl_token.programId,
+          })
+          .signers([tipper])
+          .rpc();
+        expect.fail("should have failed");
+      } catch (error: any) {
+        expect(error.toString()).to.contain("Error Code: InvalidTokenAccount");
+      }
+    });
+
+    it("should not tip with only some of the token accounts", async () => {
+      try {
+        await program.methods
+          .tipPost(new anchor.BN(1))
+          .accounts({
+            post: postPDA,
+            fromProfile: tipperProfilePDA,
+            toProfile: profilePDA,
+            authority: tipper.publicKey,
+            recipient: provider.publicKey,
+            mint,
+            senderTokenAccount,
+            recipientTokenAccount: null,
+            tokenProgram: null,
+          })
+          .signers([tipper])
+          .rpc();
+        expect.fail("should have failed");
+      } catch (error: any) {
+        expect(error.toString()).to.contain("Error Code: InvalidTokenAccount");
+      }
+    });
+  });
+
   describe("Post with session token", async () => {
     let rpcConnection: anchor.web3.Connection;
     let sessionToken: anchor.web3.PublicKey;
@@ -156,7 +544,7 @@ describe("Post", async () => {
       const randomHash = randombytes(32);
       const metadataUri = "This is a test post";
       const post = program.methods
-        .createPost(metadataUri, randomHash)
+        .createPost(metadataUri, randomHash, [], [], null)
         .accounts({
           profile: profilePDA,
           sessionToken: sessionToken,
@@ -174,7 +562,7 @@ describe("Post", async () => {
       const randomHash = randombytes(32);
       const metadataUri = "This is a test post";
       const post = program.methods
-        .createPost(metadataUri, randomHash)
+        .createPost(metadataUri, randomHash, [], [], null)
         .accounts({
           profile: randomProfilePDA,
           sessionToken: sessionToken,
@@ -194,7 +582,7 @@ describe("Post", async () => {
       const randomHash = randombytes(32);
       const metadataUri = "This is a test post";
       const post = program.methods
-        .createPost(metadataUri, randomHash)
+        .createPost(metadataUri, randomHash, [], [], null)
         .accounts({
           profile: randomProfilePDA,
           sessionToken: sessionToken,
@@ -2,7 +2,13 @@ import * as anchor from "@project-serum/anchor";
 import randombytes from "randombytes";
 import { expect } from "chai";
 import { GplCore } from "../../target/types/gpl_core";
-import { airdrop, new_session, createGumDomain, createGumTld } from "../utils";
+import {
+  airdrop,
+  new_session,
+  createGumDomain,
+  createGumTld,
+  findBlockPDA,
+} from "../utils";
 
 const program = anchor.workspace.GplCore as anchor.Program<GplCore>;
 
@@ -36,7 +42,7 @@ describe("Reaction", async () => {
     const postRandomHash = randombytes(32);
     const metadataUri = "This is a test post";
     const post = program.methods
-      .createPost(metadataUri, postRandomHash)
+      .createPost(metadataUri, postRandomHash, [], [], null)
       .accounts({ profile: profilePDA, sessionToken: null });
     const postPubKeys = await post.pubkeys();
     postPDA = postPubKeys.post as anchor.web3.PublicKey;
@@ -52,6 +58,8 @@ describe("Reaction", async () => {
       toPost: postPDA,
       fromProfile: profilePDA,
       sessionToken: null,
+      block: findBlockPDA(profilePDA, profilePDA),
+      reactionSet: null,
     });
     const reactionPubKeys = await reaction.pubkeys();
     reactionPDA = reactionPubKeys.reaction as anchor.web3.PublicKey;
@@ -89,6 +97,8 @@ describe("Reaction", async () => {
       toPost: postPDA,
       fromProfile: profilePDA,
       sessionToken: null,
+      block: findBlockPDA(profilePDA, profilePDA),
+      reactionSet: null,
     });
     const reactionPubKeys = await reaction.pubkeys();
     reactionPDA = reactionPubKeys.reaction as anchor.web3.PublicKey;
@@ -113,6 +123,490 @@ describe("Reaction", async () => {
       .rpc();
   });
 
+  it("should clean up a reaction once its post is deleted", async () => {
+    const post = program.methods
+      .createPost("This post will be deleted", randombytes(32), [], [], null)
+      .accounts({ profile: profilePDA, sessionToken: null });
+    const orphanPostPDA = (await post.pubkeys()).post as anchor.web3.PublicKey;
+    await post.rpc();
+
+    const reaction = program.methods.createReaction(emoji).accounts({
+      toPost: orphanPostPDA,
+      fromProfile: profilePDA,
+      sessionToken: null,
+      block: findBlockPDA(profilePDA, profilePDA),
+      reactionSet: null,
+    });
+    const orphanReactionPDA = (await reaction.pubkeys())
+      .reaction as anchor.web3.PublicKey;
+    await reaction.rpc();
+
+    await program.methods
+      .deletePost()
+      .accounts({
+        profile: profilePDA,
+        post: orphanPostPDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    // No signer is needed, the rent goes back to the profile authority
+    await program.methods
+      .cleanupOrphanReaction()
+      .accounts({
+        reaction: orphanReactionPDA,
+        toPost: orphanPostPDA,
+        fromProfile: profilePDA,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    try {
+      await program.account.reaction.fetch(orphanReactionPDA);
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain(
+        `Account does not exist or has no data ${orphanReactionPDA.toString()}`
+      );
+    }
+  });
+
+  it("should not clean up a reaction while its post exists", async () => {
+    const reaction = program.methods.createReaction(emoji).accounts({
+      toPost: postPDA,
+      fromProfile: profilePDA,
+      sessionToken: null,
+      block: findBlockPDA(profilePDA, profilePDA),
+      reactionSet: null,
+    });
+    reactionPDA = (await reaction.pubkeys()).reaction as anchor.web3.PublicKey;
+    await reaction.rpc();
+
+    try {
+      await program.methods
+        .cleanupOrphanReaction()
+        .accounts({
+          reaction: reactionPDA,
+          toPost: postPDA,
+          fromProfile: profilePDA,
+          refundReceiver: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: AccountNotOrphaned");
+    }
+
+    // Clean up for next tests
+    await program.methods
+      .deleteReaction()
+      .accounts({
+        toPost: postPDA,
+        fromProfile: profilePDA,
+        reaction: reactionPDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+  });
+
+  it("should update a reaction", async () => {
+    const reaction = program.methods.createReaction(emoji).accounts({
+      toPost: postPDA,
+      fromProfile: profilePDA,
+      sessionToken: null,
+      block: findBlockPDA(profilePDA, profilePDA),
+      reactionSet: null,
+    });
+    reactionPDA = (await reaction.pubkeys()).reaction as anchor.web3.PublicKey;
+    await reaction.rpc();
+
+    const updateReaction = program.methods.updateReaction("❤️").accounts({
+      reaction: reactionPDA,
+      toPost: postPDA,
+      fromProfile: profilePDA,
+      reactionSet: null,
+      sessionToken: null,
+      refundReceiver: provider.wallet.publicKey,
+    });
+    const newReactionPDA = (await updateReaction.pubkeys())
+      .newReaction as anchor.web3.PublicKey;
+    await updateReaction.rpc();
+
+    const reactionAccount = await program.account.reaction.fetch(
+      newReactionPDA
+    );
+    expect(reactionAccount.reactionType).to.equal("❤️");
+    try {
+      await program.account.reaction.fetch(reactionPDA);
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain(
+        `Account does not exist or has no data ${reactionPDA.toString()}`
+      );
+    }
+
+    // Clean up for next tests
+    await program.methods
+      .deleteReaction()
+      .accounts({
+        toPost: postPDA,
+        fromProfile: profilePDA,
+        reaction: newReactionPDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+  });
+
+  it("should only accept reaction types from a reaction set", async () => {
+    const reactionSet = program.methods.createReactionSet(
+      [...randombytes(32)],
+      [emoji, "❤️"]
+    );
+    const reactionSetPDA = (await reactionSet.pubkeys())
+      .reactionSet as anchor.web3.PublicKey;
+    await reactionSet.rpc();
+
+    try {
+      await program.methods
+        .createReaction("🤡")
+        .accounts({
+          toPost: postPDA,
+          fromProfile: profilePDA,
+          sessionToken: null,
+          block: findBlockPDA(profilePDA, profilePDA),
+          reactionSet: reactionSetPDA,
+        })
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain("Error Code: ReactionTypeNotAllowed");
+    }
+
+    const reaction = program.methods.createReaction(emoji).accounts({
+      toPost: postPDA,
+      fromProfile: profilePDA,
+      sessionToken: null,
+      block: findBlockPDA(profilePDA, profilePDA),
+      reactionSet: reactionSetPDA,
+    });
+    reactionPDA = (await reaction.pubkeys()).reaction as anchor.web3.PublicKey;
+    await reaction.rpc();
+
+    // Clean up for next tests
+    await program.methods
+      .deleteReaction()
+      .accounts({
+        toPost: postPDA,
+        fromProfile: profilePDA,
+        reaction: reactionPDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+    await program.methods
+      .deleteReactionSet()
+      .accounts({ reactionSet: reactionSetPDA })
+      .rpc();
+  });
+
+  it("should count reactions in a reaction tally", async () => {
+    // Use a post of its own, the tally is cleaned up with it
+    const post = program.methods
+      .createPost("This post has a tally", randombytes(32), [], [], null)
+      .accounts({ profile: profilePDA, sessionToken: null });
+    const tallyPostPDA = (await post.pubkeys()).post as anchor.web3.PublicKey;
+    await post.rpc();
+
+    const binaryType = randombytes(32);
+    const tally = program.methods
+      .createReactionTally([emoji], [[...binaryType]])
+      .accounts({
+        post: tallyPostPDA,
+        profile: profilePDA,
+        sessionToken: null,
+      });
+    const reactionTallyPDA = (await tally.pubkeys())
+      .reactionTally as anchor.web3.PublicKey;
+    await tally.rpc();
+
+    const reaction = program.methods.createReaction(emoji).accounts({
+      toPost: tallyPostPDA,
+      fromProfile: profilePDA,
+      sessionToken: null,
+      block: findBlockPDA(profilePDA, profilePDA),
+      reactionSet: null,
+    });
+    const tallyReactionPDA = (await reaction.pubkeys())
+      .reaction as anchor.web3.PublicKey;
+    await reaction.rpc();
+
+    // Types the author did not pick are not counted
+    await program.methods
+      .createReaction("🔥")
+      .accounts({
+        toPost: tallyPostPDA,
+        fromProfile: profilePDA,
+        sessionToken: null,
+        block: findBlockPDA(profilePDA, profilePDA),
+        reactionSet: null,
+      })
+      .rpc();
+
+    await program.methods
+      .createBinaryReaction([...binaryType])
+      .accounts({
+        toPost: tallyPostPDA,
+        fromProfile: profilePDA,
+        sessionToken: null,
+        block: findBlockPDA(profilePDA, profilePDA),
+      })
+      .rpc();
+
+    let tallyAccount = await program.account.reactionTally.fetch(
+      reactionTallyPDA
+    );
+    expect(tallyAccount.counts.length).to.equal(1);
+    expect(tallyAccount.counts[0].reactionType).to.equal(emoji);
+    expect(tallyAccount.counts[0].count.toNumber()).to.equal(1);
+    expect(tallyAccount.binaryCounts[0].count.toNumber()).to.equal(1);
+
+    await program.methods
+      .deleteReaction()
+      .accounts({
+        toPost: tallyPostPDA,
+        fromProfile: profilePDA,
+        reaction: tallyReactionPDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    tallyAccount = await program.account.reactionTally.fetch(reactionTallyPDA);
+    expect(tallyAccount.counts[0].count.toNumber()).to.equal(0);
+
+    // The tally of a deleted post can be closed by anyone
+    await program.methods
+      .deletePost()
+      .accounts({
+        profile: profilePDA,
+        post: tallyPostPDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .cleanupOrphanReactionTally()
+      .accounts({
+        reactionTally: reactionTallyPDA,
+        post: tallyPostPDA,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const closedTally = await program.account.reactionTally.fetchNullable(
+      reactionTallyPDA
+    );
+    expect(closedTally).to.be.null;
+  });
+
+  it("should not uncount a reaction made before the reaction tally", async () => {
+    const post = program.methods
+      .createPost("This post gets a tally later", randombytes(32), [], [], null)
+      .accounts({ profile: profilePDA, sessionToken: null });
+    const tallyPostPDA = (await post.pubkeys()).post as anchor.web3.PublicKey;
+    await post.rpc();
+
+    // Reacted before the tally exists, so it is never counted
+    const earlyReaction = program.methods.createReaction(emoji).accounts({
+      toPost: tallyPostPDA,
+      fromProfile: profilePDA,
+      sessionToken: null,
+      block: findBlockPDA(profilePDA, profilePDA),
+      reactionSet: null,
+    });
+    const earlyReactionPDA = (await earlyReaction.pubkeys())
+      .reaction as anchor.web3.PublicKey;
+    await earlyReaction.rpc();
+    expect(
+      (await program.account.reaction.fetch(earlyReactionPDA)).counted
+    ).to.equal(false);
+
+    const tally = program.methods
+      .createReactionTally([emoji], [])
+      .accounts({
+        post: tallyPostPDA,
+        profile: profilePDA,
+        sessionToken: null,
+      });
+    const reactionTallyPDA = (await tally.pubkeys())
+      .reactionTally as anchor.web3.PublicKey;
+    await tally.rpc();
+
+    const otherUser = anchor.web3.Keypair.generate();
+    await airdrop(otherUser.publicKey);
+    const screenName = await createGumDomain(
+      await createGumTld(),
+      "latereactor",
+      otherUser
+    );
+    const otherProfile = program.methods
+      .createProfile(randombytes(32), "https://example.com")
+      .accounts({
+        payer: otherUser.publicKey,
+        authority: otherUser.publicKey,
+        screenName,
+      })
+      .signers([otherUser]);
+    const otherProfilePDA = (await otherProfile.pubkeys())
+      .profile as anchor.web3.PublicKey;
+    await otherProfile.rpc();
+
+    await program.methods
+      .createReaction(emoji)
+      .accounts({
+        toPost: tallyPostPDA,
+        fromProfile: otherProfilePDA,
+        sessionToken: null,
+        block: findBlockPDA(profilePDA, otherProfilePDA),
+        reactionSet: null,
+        payer: otherUser.publicKey,
+        authority: otherUser.publicKey,
+      })
+      .signers([otherUser])
+      .rpc();
+
+    await program.methods
+      .deleteReaction()
+      .accounts({
+        toPost: tallyPostPDA,
+        fromProfile: profilePDA,
+        reaction: earlyReactionPDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const tallyAccount = await program.account.reactionTally.fetch(
+      reactionTallyPDA
+    );
+    expect(tallyAccount.counts[0].count.toNumber()).to.equal(1);
+  });
+
+  it("should only let the post author create a reaction tally", async () => {
+    const otherUser = anchor.web3.Keypair.generate();
+    await airdrop(otherUser.publicKey);
+    try {
+      await program.methods
+        .createReactionTally([emoji], [])
+        .accounts({
+          post: postPDA,
+          profile: profilePDA,
+          sessionToken: null,
+          payer: otherUser.publicKey,
+          authority: otherUser.publicKey,
+        })
+        .signers([otherUser])
+        .rpc();
+      expect.fail("should have failed");
+    } catch (error: any) {
+      expect(error.toString()).to.contain("Error Code: UnauthorizedSigner");
+    }
+  });
+
+  it("should create and delete a binary reaction", async () => {
+    const reactionType = randombytes(32);
+    const reaction = program.methods
+      .createBinaryReaction([...reactionType])
+      .accounts({
+        toPost: postPDA,
+        fromProfile: profilePDA,
+        sessionToken: null,
+        block: findBlockPDA(profilePDA, profilePDA),
+      });
+    const binaryReactionPDA = (await reaction.pubkeys())
+      .reaction as anchor.web3.PublicKey;
+    await reaction.rpc();
+
+    const reactionAccount = await program.account.binaryReaction.fetch(
+      binaryReactionPDA
+    );
+    expect(Buffer.from(reactionAccount.reactionType).equals(reactionType)).to.be
+      .true;
+    expect(reactionAccount.toPost.toBase58()).to.equal(postPDA.toBase58());
+
+    await program.methods
+      .deleteBinaryReaction()
+      .accounts({
+        reaction: binaryReactionPDA,
+        toPost: postPDA,
+        fromProfile: profilePDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    try {
+      await program.account.binaryReaction.fetch(binaryReactionPDA);
+    } catch (error: any) {
+      expect(error).to.be.an("error");
+      expect(error.toString()).to.contain(
+        `Account does not exist or has no data ${binaryReactionPDA.toString()}`
+      );
+    }
+  });
+
+  it("should clean up a binary reaction once its post is deleted", async () => {
+    const post = program.methods
+      .createPost("This post will be deleted", randombytes(32), [], [], null)
+      .accounts({ profile: profilePDA, sessionToken: null });
+    const orphanPostPDA = (await post.pubkeys()).post as anchor.web3.PublicKey;
+    await post.rpc();
+
+    const reaction = program.methods
+      .createBinaryReaction([...randombytes(32)])
+      .accounts({
+        toPost: orphanPostPDA,
+        fromProfile: profilePDA,
+        sessionToken: null,
+        block: findBlockPDA(profilePDA, profilePDA),
+      });
+    const orphanReactionPDA = (await reaction.pubkeys())
+      .reaction as anchor.web3.PublicKey;
+    await reaction.rpc();
+
+    await program.methods
+      .deletePost()
+      .accounts({
+        profile: profilePDA,
+        post: orphanPostPDA,
+        sessionToken: null,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    // No signer is needed, the rent goes back to the profile authority
+    await program.methods
+      .cleanupOrphanBinaryReaction()
+      .accounts({
+        reaction: orphanReactionPDA,
+        toPost: orphanPostPDA,
+        fromProfile: profilePDA,
+        refundReceiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const reactionAccount = await program.account.binaryReaction.fetchNullable(
+      orphanReactionPDA
+    );
+    expect(reactionAccount).to.be.null;
+  });
+
   describe("Reaction with session token", async () => {
     let sessionToken: anchor.web3.PublicKey;
     let sessionKeypair: anchor.web3.Keypair;
@@ -132,6 +626,8 @@ describe("Reaction", async () => {
         toPost: postPDA,
         fromProfile: profilePDA,
         sessionToken: sessionToken,
+        block: findBlockPDA(profilePDA, profilePDA),
+        reactionSet: null,
         authority: sessionKeypair.publicKey,
       });
       const reactionPubKeys = await reaction.pubkeys();
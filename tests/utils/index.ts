@@ -13,6 +13,7 @@ import { GplNameservice } from "../../target/types/gpl_nameservice";
 import { GplSession } from "../../target/types/gpl_session";
 
 import pkg from "js-sha3";
+import fs from "fs";
 
 import {
   getConcurrentMerkleTreeAccountSize,
@@ -153,6 +154,130 @@ export function hash(data: Buffer): Buffer {
   return Buffer.from(keccak_256.arrayBuffer(data));
 }
 
+// The block of blocker -> blocked, interactions pass it whether it exists or not
+export function findBlockPDA(
+  blocker: PublicKey,
+  blocked: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("block"), blocker.toBuffer(), blocked.toBuffer()],
+    gpl_core.programId
+  )[0];
+}
+
+// Follows keep the seeds of the connections created before kinds existed
+export function findConnectionPDA(
+  fromProfile: PublicKey,
+  toProfile: PublicKey,
+  kind: number
+): PublicKey {
+  const seeds = [
+    Buffer.from("connection"),
+    fromProfile.toBuffer(),
+    toProfile.toBuffer(),
+  ];
+  if (kind !== 0) {
+    seeds.push(Buffer.from([kind]));
+  }
+  return PublicKey.findProgramAddressSync(seeds, gpl_core.programId)[0];
+}
+
+export const spl_token = anchor.Spl.token();
+
+export function loadKeypair(path: string): Keypair {
+  return Keypair.fromSecretKey(
+    Uint8Array.from(JSON.parse(fs.readFileSync(path, "utf-8")))
+  );
+}
+
+// The token metadata fixtures loaded by Anchor.toml are for these mints, both
+// are part of fixtureCollection but only the first one is verified
+export const fixtureCollection = new PublicKey(
+  "DPzY1h8ZHJEVa1VNsNhKdXAPiCkF3c6LjUNvtTnBs3gW"
+);
+export const verifiedCollectionMint = loadKeypair(
+  "test_keys/collection_verified_mint.json"
+);
+export const unverifiedCollectionMint = loadKeypair(
+  "test_keys/collection_unverified_mint.json"
+);
+
+export function findMetadataPDA(mint: PublicKey): PublicKey {
+  const metadataProgram = new PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), metadataProgram.toBuffer(), mint.toBuffer()],
+    metadataProgram
+  )[0];
+}
+
+// The provider wallet is the mint authority
+export async function createMint(
+  mint: Keypair = Keypair.generate()
+): Promise<PublicKey> {
+  await spl_token.methods
+    .initializeMint(0, provider.publicKey, null)
+    .accounts({
+      mint: mint.publicKey,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    })
+    .preInstructions([
+      SystemProgram.createAccount({
+        fromPubkey: provider.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space: 82,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(
+          82
+        ),
+        programId: spl_token.programId,
+      }),
+    ])
+    .signers([mint])
+    .rpc();
+  return mint.publicKey;
+}
+
+export async function createTokenAccount(
+  mint: PublicKey,
+  owner: PublicKey,
+  amount: number
+): Promise<PublicKey> {
+  const account = Keypair.generate();
+  await spl_token.methods
+    .initializeAccount()
+    .accounts({
+      account: account.publicKey,
+      mint,
+      authority: owner,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    })
+    .preInstructions([
+      SystemProgram.createAccount({
+        fromPubkey: provider.publicKey,
+        newAccountPubkey: account.publicKey,
+        space: 165,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(
+          165
+        ),
+        programId: spl_token.programId,
+      }),
+    ])
+    .postInstructions([
+      await spl_token.methods
+        .mintTo(new anchor.BN(amount))
+        .accounts({
+          mint,
+          to: account.publicKey,
+          authority: provider.publicKey,
+        })
+        .instruction(),
+    ])
+    .signers([account])
+    .rpc();
+  return account.publicKey;
+}
+
 async function find_asset_id(
   merkleTree: PublicKey,
   seedHash: Buffer
@@ -164,6 +289,81 @@ async function find_asset_id(
   return asset_id;
 }
 
+// Resolves with the next event of the given name emitted by the program
+export function waitForEvent(
+  program: anchor.Program<any>,
+  name: string
+): Promise<any> {
+  return new Promise((resolve) => {
+    const listener = program.addEventListener(name, (event) => {
+      program.removeEventListener(listener);
+      resolve(event);
+    });
+  });
+}
+
+// The compressed leaves hash frozen layouts of the gpl_core accounts, fields
+// added to these accounts later are not part of the leaves, the names keep the
+// discriminators of the gpl_core accounts
+const frozenLeafCoder = new anchor.BorshAccountsCoder({
+  version: "0.1.0",
+  name: "gpl_compression_leaves",
+  instructions: [],
+  accounts: [
+    {
+      name: "Post",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "profile", type: "publicKey" },
+          { name: "metadataUri", type: "string" },
+          { name: "randomHash", type: { array: ["u8", 32] } },
+          { name: "replyTo", type: { option: "publicKey" } },
+        ],
+      },
+    },
+    {
+      name: "Connection",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "fromProfile", type: "publicKey" },
+          { name: "toProfile", type: "publicKey" },
+        ],
+      },
+    },
+    {
+      name: "Reaction",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "fromProfile", type: "publicKey" },
+          { name: "toPost", type: "publicKey" },
+          { name: "reactionType", type: "string" },
+        ],
+      },
+    },
+    {
+      name: "Badge",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "issuer", type: "publicKey" },
+          { name: "holder", type: "publicKey" },
+          { name: "updateAuthority", type: "publicKey" },
+          { name: "schema", type: "publicKey" },
+          { name: "metadataUri", type: "string" },
+          { name: "expiresAt", type: { option: "i64" } },
+          { name: "revoked", type: "bool" },
+          { name: "attributes", type: "bytes" },
+          { name: "pending", type: "bool" },
+        ],
+      },
+    },
+  ],
+});
+const frozenLeaves = ["Post", "Connection", "Reaction", "Badge"];
+
 export async function to_leaf(
   merkleTree: PublicKey,
   name: any,
@@ -172,12 +372,29 @@ export async function to_leaf(
 ): Promise<Buffer> {
   const seedHash = hash(Buffer.concat(seeds));
   const assetId = await find_asset_id(merkleTree, seedHash);
-  const dataSerialized = await gpl_core.coder.accounts.encode(name, data);
+  const coder = frozenLeaves.includes(name)
+    ? frozenLeafCoder
+    : gpl_core.coder.accounts;
+  const dataSerialized = await coder.encode(name, data);
   const dataHash = hash(dataSerialized);
   const leaf = Buffer.concat([assetId.toBuffer(), seedHash, dataHash]);
   return hash(leaf);
 }
 
+// Compressed comments are hashed without the discriminator
+export async function to_comment_leaf(
+  merkleTree: PublicKey,
+  data: any,
+  seeds: Buffer[]
+): Promise<Buffer> {
+  const seedHash = hash(Buffer.concat(seeds));
+  const assetId = await find_asset_id(merkleTree, seedHash);
+  const dataSerialized = await frozenLeafCoder.encode("Post", data);
+  const dataHash = hash(dataSerialized.subarray(8));
+  const leaf = Buffer.concat([assetId.toBuffer(), seedHash, dataHash]);
+  return hash(leaf);
+}
+
 export async function setupTree(
   payer: Keypair,
   depthSizePair: ValidDepthSizePair,